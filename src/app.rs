@@ -1,11 +1,95 @@
-use std::sync::mpsc::Receiver;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use ratatui::widgets::ListState;
 
-use crate::plugin::{GitSource, LinkTarget, Plugin, PluginError, PluginManager};
+use crate::filter::{self, SkillFilter};
+use crate::manifest;
+use crate::marks::{self, Marks};
+use crate::plugin::{
+    Component, ComponentKind, FetchAllReceiver, Fetched, Health, LinkEntry, LinkMode, LinkStatus,
+    LinkTarget, Marketplace, MarketplaceEntry, PendingUpdate, Plugin, PluginError, PluginManager,
+    Repair, SearchIndex, SearchResult, SearchSource, Settings, Skill, parse_local_path,
+};
 use crate::status::{StatusKind, StatusManager};
 
+/// Receiver for a fetched update awaiting review.
+type FetchReceiver = Receiver<Result<PendingUpdate, PluginError>>;
+
+/// Receiver for background update checks, one message per plugin path.
+type CheckReceiver = Receiver<(PathBuf, Result<Option<usize>, PluginError>)>;
+
+/// Receiver for a background marketplace add or update, with a summary on success.
+type MarketplaceReceiver = Receiver<Result<String, PluginError>>;
+
+/// Receiver for background doctor repairs: the message to show, and whether all succeeded.
+type RepairReceiver = Receiver<Result<String, String>>;
+
+/// Progress of an "update all" run.
+pub struct BatchUpdate {
+    pub rx: FetchAllReceiver,
+    pub total: usize,
+    pub done: usize,
+    /// Plugins with updates queued for review.
+    pub reviews: usize,
+    /// Plugins that failed to update, with the reason.
+    pub failures: Vec<(String, PluginError)>,
+    /// Keeps the plugins being updated busy until the run ends.
+    _busy: Vec<BusyGuard>,
+}
+
+/// Paths of plugins with a git operation running, shared with background threads
+/// so two operations never work on the same clone at once.
+#[derive(Clone, Default)]
+pub struct BusyPlugins(Arc<Mutex<HashSet<PathBuf>>>);
+
+impl BusyPlugins {
+    /// Mark a plugin busy until the guard is dropped, unless it already is.
+    pub fn claim(&self, path: &Path) -> Option<BusyGuard> {
+        let mut paths = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        paths.insert(path.to_path_buf()).then(|| BusyGuard {
+            busy: self.clone(),
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// Keeps a plugin marked busy while alive.
+pub struct BusyGuard {
+    busy: BusyPlugins,
+    path: PathBuf,
+}
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        let mut paths = self.busy.0.lock().unwrap_or_else(|e| e.into_inner());
+        paths.remove(&self.path);
+    }
+}
+
+/// A fetched update waiting for the user to accept or reject it.
+pub struct UpdateReview {
+    pub plugin: Arc<Plugin>,
+    pub name: String,
+    pub pending: PendingUpdate,
+}
+
+/// The SKILL.md shown in the skill list preview pane.
+pub struct SkillPreview {
+    /// The SKILL.md file.
+    pub path: PathBuf,
+    /// When the file was last modified, to notice changes on disk.
+    modified: Option<SystemTime>,
+    pub content: String,
+    /// Other files in the skill directory, relative to it.
+    pub files: Vec<PathBuf>,
+    pub scroll: u16,
+}
+
 /// The current view in the TUI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum View {
@@ -13,6 +97,41 @@ pub enum View {
     SkillList,
     LinkTargetSelect,
     InstallInput,
+    UpdatePreview,
+    SkillDetail,
+    Health,
+    Available,
+    MarketplaceInput,
+    GlobalSearch,
+    AllSkills,
+}
+
+/// The tabs of the skill list: skills, then one per kind of bundle component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Skills,
+    Components(ComponentKind),
+}
+
+impl Tab {
+    /// Get all tabs, in display order.
+    pub fn all() -> Vec<Tab> {
+        std::iter::once(Tab::Skills)
+            .chain(
+                ComponentKind::all()
+                    .iter()
+                    .map(|&kind| Tab::Components(kind)),
+            )
+            .collect()
+    }
+
+    /// Get the name shown on the tab.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tab::Skills => "Skills",
+            Tab::Components(kind) => kind.label(),
+        }
+    }
 }
 
 /// Application state.
@@ -21,8 +140,25 @@ pub struct App {
     pub plugins: Vec<Arc<Plugin>>,
     pub installing: Vec<(String, Receiver<Result<Arc<Plugin>, PluginError>>)>,
     pub updating: Vec<(usize, String, Receiver<Result<Plugin, PluginError>>)>,
+    pub fetching: Vec<(Arc<Plugin>, String, FetchReceiver)>,
+    pub rolling_back: Vec<(String, Receiver<Result<Plugin, PluginError>>)>,
+    pub reviews: Vec<UpdateReview>,
+    pub preview_scroll: u16,
+    pub batch_update: Option<BatchUpdate>,
+    pub checking: Option<CheckReceiver>,
+    pub check_failures: usize,
+    /// Number of upstream commits each plugin is behind, keyed by plugin path.
+    pub behind: HashMap<PathBuf, usize>,
+    /// Plugins being fetched, updated or rolled back.
+    pub busy: BusyPlugins,
+    pub show_skill_preview: bool,
+    pub skill_preview: Option<SkillPreview>,
     pub selected_plugin: usize,
     pub selected_skill: usize,
+    /// The skill list tab being shown.
+    pub tab: Tab,
+    /// Index of the selected component among those of the current tab's kind.
+    pub selected_component: usize,
     pub plugin_list_state: ListState,
     pub skill_list_state: ListState,
     pub view: View,
@@ -32,21 +168,61 @@ pub struct App {
     pub search_active: bool,
     pub search_query: String,
     pub link_target_selection: usize,
+    /// Entries of the link targets, as of the last doctor scan.
+    pub health: Vec<LinkEntry>,
+    pub health_selection: usize,
+    /// The running repair, with its status entry; repairs may install plugins.
+    pub repairing: Option<(String, RepairReceiver)>,
+    /// Added marketplaces, as of when the Available view was last opened.
+    pub marketplaces: Vec<Marketplace>,
+    /// Marketplace entries that are installed, by marketplace and entry name;
+    /// checked when the marketplaces are loaded and after installs and removals.
+    pub installed_entries: HashSet<(String, String)>,
+    /// Index into [`App::available_entries`].
+    pub available_selection: usize,
+    pub marketplace_tasks: Vec<(String, MarketplaceReceiver)>,
+    /// Index of installed and available skills, built when global search is opened.
+    pub search_index: SearchIndex,
+    pub global_query: String,
+    pub global_results: Vec<SearchResult>,
+    pub global_selection: usize,
+    pub all_skills_filter: SkillFilter,
+    /// Index into the link targets that the all-skills filter and actions apply to;
+    /// all targets if unset.
+    pub all_skills_target: Option<usize>,
+    /// Index into [`App::all_skills`].
+    pub all_skills_selection: usize,
+    /// The skills marked in the skill list.
+    pub marks: Marks,
 }
 
 impl App {
     /// Create a new App instance.
     pub fn new() -> Result<Self, PluginError> {
-        let manager = PluginManager::new()?;
+        let settings = Settings::get()?;
+        let manager = manifest::with_project_lockfile(PluginManager::new()?);
         let plugins = manager.list_installed()?;
 
-        Ok(Self {
+        let mut app = Self {
             manager,
             plugins,
             installing: Vec::new(),
             updating: Vec::new(),
+            fetching: Vec::new(),
+            rolling_back: Vec::new(),
+            reviews: Vec::new(),
+            preview_scroll: 0,
+            batch_update: None,
+            checking: None,
+            check_failures: 0,
+            behind: HashMap::new(),
+            busy: BusyPlugins::default(),
+            show_skill_preview: settings.show_preview.value,
+            skill_preview: None,
             selected_plugin: 0,
             selected_skill: 0,
+            tab: Tab::Skills,
+            selected_component: 0,
             plugin_list_state: ListState::default().with_selected(Some(0)),
             skill_list_state: ListState::default().with_selected(Some(0)),
             view: View::PluginList,
@@ -56,7 +232,26 @@ impl App {
             search_active: false,
             search_query: String::new(),
             link_target_selection: 0,
-        })
+            health: Vec::new(),
+            health_selection: 0,
+            repairing: None,
+            marketplaces: Vec::new(),
+            installed_entries: HashSet::new(),
+            available_selection: 0,
+            marketplace_tasks: Vec::new(),
+            search_index: SearchIndex::default(),
+            global_query: String::new(),
+            global_results: Vec::new(),
+            global_selection: 0,
+            all_skills_filter: SkillFilter::All,
+            all_skills_target: None,
+            all_skills_selection: 0,
+            marks: Marks::default(),
+        };
+        if settings.check_updates.value {
+            app.start_update_check();
+        }
+        Ok(app)
     }
 
     /// Refresh the plugin list.
@@ -64,47 +259,258 @@ impl App {
         match self.manager.list_installed() {
             Ok(plugins) => {
                 self.plugins = plugins;
-                self.selected_plugin = self.selected_plugin.min(self.plugins.len().saturating_sub(1));
-                self.status.add("refresh", "Refreshed plugin list", StatusKind::Success);
+                self.selected_plugin = self
+                    .selected_plugin
+                    .min(self.plugins.len().saturating_sub(1));
+                self.check_installed_entries();
+                self.status
+                    .add("refresh", "Refreshed plugin list", StatusKind::Success);
+                self.start_update_check();
             }
             Err(e) => {
-                self.status.add("refresh", format!("Error: {}", e), StatusKind::Error);
+                self.status
+                    .add("refresh", format!("Error: {}", e), StatusKind::Error);
+            }
+        }
+    }
+
+    /// Check every plugin for upstream commits in the background.
+    pub fn start_update_check(&mut self) {
+        if self.checking.is_some() {
+            return;
+        }
+
+        let manager = self.manager.clone();
+        let plugins = self.plugins.clone();
+        let busy = self.busy.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            for plugin in plugins {
+                // Leave plugins alone while they are updated or rolled back
+                let Some(_guard) = busy.claim(&plugin.path) else {
+                    continue;
+                };
+                let result = manager.commits_behind(&plugin);
+                if tx.send((plugin.path.clone(), result)).is_err() {
+                    return;
+                }
+            }
+        });
+
+        self.check_failures = 0;
+        self.checking = Some(rx);
+    }
+
+    /// Fetch every installed plugin through the manager's worker pool.
+    ///
+    /// As with [`App::update_selected`], fetched updates are queued for review
+    /// and only applied once accepted; local and pinned plugins are rescanned.
+    pub fn update_all(&mut self) {
+        if self.batch_update.is_some() {
+            self.status.add(
+                "update:all",
+                "Update all is already running",
+                StatusKind::Info,
+            );
+            return;
+        }
+        if self.plugins.is_empty() {
+            self.status
+                .add("update:error", "No plugins installed", StatusKind::Error);
+            return;
+        }
+
+        // Plugins busy with another operation are left out of the run
+        let (plugins, guards): (Vec<_>, Vec<_>) = self
+            .plugins
+            .iter()
+            .filter_map(|p| Some((Arc::clone(p), self.busy.claim(&p.path)?)))
+            .unzip();
+        if plugins.is_empty() {
+            self.status.add(
+                "update:error",
+                "All plugins are busy, try again shortly",
+                StatusKind::Error,
+            );
+            return;
+        }
+
+        let total = plugins.len();
+        self.status.add(
+            "update:all",
+            format!("Fetching 0/{}...", total),
+            StatusKind::Progress,
+        );
+        self.batch_update = Some(BatchUpdate {
+            rx: self.manager.fetch_all(plugins),
+            total,
+            done: 0,
+            reviews: 0,
+            failures: Vec::new(),
+            _busy: guards,
+        });
+    }
+
+    /// Poll for results of an "update all" run.
+    pub fn poll_update_all(&mut self) {
+        let Some(batch) = &mut self.batch_update else {
+            return;
+        };
+
+        loop {
+            match batch.rx.try_recv() {
+                Ok((plugin, result)) => {
+                    batch.done += 1;
+                    let name = format!("{}/{}", plugin.owner, plugin.name());
+                    match result {
+                        Ok(Fetched::Pending(pending)) if pending.is_up_to_date() => {}
+                        Ok(Fetched::Pending(pending)) => {
+                            batch.reviews += 1;
+                            self.reviews.push(UpdateReview {
+                                plugin,
+                                name,
+                                pending,
+                            });
+                        }
+                        Ok(Fetched::Rescanned(updated)) => {
+                            if let Some(idx) =
+                                self.plugins.iter().position(|p| p.path == updated.path)
+                            {
+                                self.plugins[idx] = Arc::new(*updated);
+                            }
+                        }
+                        Err(e) => batch.failures.push((name, e)),
+                    }
+                }
+                Err(TryRecvError::Empty) => {
+                    let message = format!("Fetching {}/{}...", batch.done, batch.total);
+                    self.status.add("update:all", message, StatusKind::Progress);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        let Some(batch) = self.batch_update.take() else {
+            return;
+        };
+        let summary = match batch.reviews {
+            0 => format!("Fetched {} plugins, no updates", batch.total),
+            n => format!(
+                "Fetched {} plugins, {} with updates to review",
+                batch.total, n
+            ),
+        };
+        if batch.failures.is_empty() {
+            self.status.add("update:all", summary, StatusKind::Success);
+        } else {
+            let failed: Vec<String> = batch
+                .failures
+                .iter()
+                .map(|(name, e)| format!("{} ({})", name, e.to_string().trim()))
+                .collect();
+            self.status.add(
+                "update:all",
+                format!("{}; failed: {}", summary, failed.join(", ")),
+                StatusKind::Error,
+            );
+        }
+
+        if self.view == View::PluginList && !self.search_active && !self.reviews.is_empty() {
+            self.preview_scroll = 0;
+            self.view = View::UpdatePreview;
+        }
+    }
+
+    /// Poll for background update check results.
+    pub fn poll_update_checks(&mut self) {
+        let Some(rx) = &self.checking else {
+            return;
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok((path, Ok(Some(count)))) if count > 0 => {
+                    self.behind.insert(path, count);
+                }
+                Ok((path, Ok(_))) => {
+                    self.behind.remove(&path);
+                }
+                Ok((_, Err(_))) => self.check_failures += 1,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => break,
             }
         }
+
+        self.checking = None;
+        let outdated = self.behind.len();
+        if self.check_failures > 0 {
+            self.status.add(
+                "check",
+                format!("Update check failed for {} plugins", self.check_failures),
+                StatusKind::Error,
+            );
+        } else if outdated > 0 {
+            self.status.add(
+                "check",
+                format!("Updates available for {} plugins", outdated),
+                StatusKind::Info,
+            );
+        }
     }
 
     /// Start installing a plugin from the current input URL in the background.
     pub fn start_install(&mut self) {
         let url = self.input.trim().to_string();
         if url.is_empty() {
-            self.status.add("install:error", "URL cannot be empty", StatusKind::Error);
+            self.status
+                .add("install:error", "URL cannot be empty", StatusKind::Error);
             return;
         }
 
-        // Parse URL to check if already installed
-        let source = match GitSource::parse(&url) {
-            Ok(s) => s,
+        if self.install_url(url) {
+            self.input.clear();
+            self.view = View::PluginList;
+        }
+    }
+
+    /// Start installing a plugin from a URL or local path in the background.
+    ///
+    /// Returns false if the URL is invalid; URLs of installed plugins are
+    /// reported instead of installed again.
+    fn install_url(&mut self, url: String) -> bool {
+        // Parse URL to check if already installed (local directories are just rescanned)
+        let source = match self.manager.parse_source(&url) {
+            _ if parse_local_path(&url).is_some() => None,
+            Ok(s) => Some(s),
             Err(e) => {
-                self.status.add("install:error", format!("Invalid URL: {}", e), StatusKind::Error);
-                return;
+                self.status.add(
+                    "install:error",
+                    format!("Invalid URL: {}", e),
+                    StatusKind::Error,
+                );
+                return false;
             }
         };
 
         // Check if already installed
-        if self.manager.is_installed(&source) {
-            self.input.clear();
-            self.view = View::PluginList;
+        if let Some(source) = source
+            && self.manager.is_installed(&source)
+        {
             self.status.add(
                 format!("install:{}", url),
-                format!("Already installed: {}/{}", source.owner, source.repo),
+                format!("Already installed: {}/{}", source.owner, source.name()),
                 StatusKind::Info,
             );
-            return;
+            return true;
         }
 
-        self.input.clear();
-        self.view = View::PluginList;
-        self.status.add(format!("install:{}", url), format!("Installing {}...", url), StatusKind::Progress);
+        self.status.add(
+            format!("install:{}", url),
+            format!("Installing {}...", url),
+            StatusKind::Progress,
+        );
 
         let manager = self.manager.clone();
         let (tx, rx) = std::sync::mpsc::channel();
@@ -116,6 +522,7 @@ impl App {
         });
 
         self.installing.push((url, rx));
+        true
     }
 
     /// Poll for completed background installations.
@@ -128,22 +535,55 @@ impl App {
             }
         }
 
+        if !completed.is_empty() {
+            self.check_installed_entries();
+        }
+
         // Remove completed in reverse order to preserve indices
         for (i, url, result) in completed.into_iter().rev() {
             self.installing.remove(i);
             let status_id = format!("install:{}", url);
             match result {
                 Ok(plugin) => {
-                    self.status.add(&status_id, format!("Installed: {}/{}", plugin.owner, plugin.name()), StatusKind::Success);
-                    self.plugins.push(plugin);
+                    self.status.add(
+                        &status_id,
+                        format!("Installed: {}/{}", plugin.owner, plugin.name()),
+                        StatusKind::Success,
+                    );
+                    // Registering a local directory again rescans it in place
+                    match self.plugins.iter().position(|p| p.path == plugin.path) {
+                        Some(idx) => self.plugins[idx] = plugin,
+                        None => self.plugins.push(plugin),
+                    }
                 }
                 Err(e) => {
-                    self.status.add(&status_id, format!("Install failed ({}): {}", url, e), StatusKind::Error);
+                    self.status.add(
+                        &status_id,
+                        format!("Install failed ({}): {}", url, e),
+                        StatusKind::Error,
+                    );
                 }
             }
         }
     }
 
+    /// Mark a plugin busy for an operation, reporting under `status_id` if it already is.
+    fn claim_plugin(&mut self, plugin: &Plugin, status_id: &str) -> Option<BusyGuard> {
+        let guard = self.busy.claim(&plugin.path);
+        if guard.is_none() {
+            self.status.add(
+                status_id,
+                format!(
+                    "{}/{} is busy, try again shortly",
+                    plugin.owner,
+                    plugin.name()
+                ),
+                StatusKind::Error,
+            );
+        }
+        guard
+    }
+
     /// Check if the selected item is a plugin being installed.
     pub fn is_selected_installing(&self) -> bool {
         self.selected_plugin >= self.plugins.len()
@@ -152,27 +592,46 @@ impl App {
     /// Delete the currently selected plugin.
     pub fn delete_selected(&mut self) {
         if self.plugins.is_empty() {
-            self.status.add("delete:error", "No plugin selected", StatusKind::Error);
+            self.status
+                .add("delete:error", "No plugin selected", StatusKind::Error);
             return;
         }
 
         if self.is_selected_installing() {
-            self.status.add("delete:error", "Plugin is still installing", StatusKind::Error);
+            self.status.add(
+                "delete:error",
+                "Plugin is still installing",
+                StatusKind::Error,
+            );
             return;
         }
 
-        let plugin = &self.plugins[self.selected_plugin];
+        let plugin = Arc::clone(&self.plugins[self.selected_plugin]);
         let name = format!("{}/{}", plugin.owner, plugin.name());
         let status_id = format!("delete:{}", name);
+        let Some(_guard) = self.claim_plugin(&plugin, &status_id) else {
+            return;
+        };
 
-        match plugin.remove() {
+        match self.manager.remove(&plugin) {
             Ok(()) => {
                 self.plugins.remove(self.selected_plugin);
-                self.selected_plugin = self.selected_plugin.min(self.plugins.len().saturating_sub(1));
-                self.status.add(&status_id, format!("Deleted: {}", name), StatusKind::Success);
+                self.selected_plugin = self
+                    .selected_plugin
+                    .min(self.plugins.len().saturating_sub(1));
+                self.check_installed_entries();
+                self.status.add(
+                    &status_id,
+                    format!("Deleted: {}", name),
+                    StatusKind::Success,
+                );
             }
             Err(e) => {
-                self.status.add(&status_id, format!("Delete failed: {}", e), StatusKind::Error);
+                self.status.add(
+                    &status_id,
+                    format!("Delete failed: {}", e),
+                    StatusKind::Error,
+                );
             }
         }
     }
@@ -182,6 +641,165 @@ impl App {
         self.plugins.get(self.selected_plugin)
     }
 
+    /// Get the currently selected skill, if the skills tab is shown.
+    pub fn selected_skill(&self) -> Option<&Skill> {
+        if self.tab != Tab::Skills {
+            return None;
+        }
+        self.selected_plugin()?.skills().get(self.selected_skill)
+    }
+
+    /// Get the skills the skill list's actions apply to: the marked ones, or
+    /// else the selected one.
+    pub fn selected_skills(&self) -> Vec<&Skill> {
+        let Some(plugin) = self.selected_plugin().filter(|_| self.tab == Tab::Skills) else {
+            return Vec::new();
+        };
+        let marked = self.marked_skill_indices();
+        if marked.is_empty() {
+            return self.selected_skill().into_iter().collect();
+        }
+        marked
+            .iter()
+            .filter_map(|&i| plugin.skills().get(i))
+            .collect()
+    }
+
+    /// Get the indices of the marked skills, including a range being selected.
+    ///
+    /// Marks of skills that are gone after a rescan are left out.
+    pub fn marked_skill_indices(&self) -> Vec<usize> {
+        self.marks.indices(
+            &self.skill_names(),
+            &self.filtered_skill_indices(),
+            self.selected_skill,
+        )
+    }
+
+    /// Get the names of the selected plugin's skills.
+    fn skill_names(&self) -> Vec<&str> {
+        self.selected_plugin()
+            .map(|p| p.skills().iter().map(|s| s.name.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Mark or unmark the selected skill, then move to the next one.
+    pub fn toggle_mark(&mut self) {
+        let Some(name) = self
+            .selected_skill()
+            .filter(|_| self.tab == Tab::Skills)
+            .map(|s| s.name.clone())
+        else {
+            return;
+        };
+        self.marks.toggle(&name);
+        self.select_next();
+    }
+
+    /// Start a range selection at the selected skill, or mark the range made so far.
+    pub fn toggle_range_mark(&mut self) {
+        if self.tab != Tab::Skills {
+            return;
+        }
+        let Some(plugin) = self.selected_plugin().cloned() else {
+            return;
+        };
+        let skills: Vec<&str> = plugin.skills().iter().map(|s| s.name.as_str()).collect();
+        let filtered = self.filtered_skill_indices();
+        self.marks
+            .toggle_range(&skills, &filtered, self.selected_skill);
+    }
+
+    /// Mark every skill matching the search, or unmark them if all are marked.
+    pub fn mark_all_filtered(&mut self) {
+        if self.tab != Tab::Skills {
+            return;
+        }
+        let Some(plugin) = self.selected_plugin().cloned() else {
+            return;
+        };
+        let skills: Vec<&str> = plugin.skills().iter().map(|s| s.name.as_str()).collect();
+        let filtered = self.filtered_skill_indices();
+        self.marks.toggle_all(&skills, &filtered);
+    }
+
+    /// Check if any skill is marked or a range selection is in progress.
+    pub fn has_marks(&self) -> bool {
+        self.marks.any(&self.skill_names())
+    }
+
+    /// Unmark every skill.
+    pub fn clear_marks(&mut self) {
+        self.marks.clear();
+    }
+
+    /// Summarize an action on one or more skills in a single status entry; see
+    /// [`marks::report`].
+    fn report_skills(
+        &mut self,
+        status_id: String,
+        done: &str,
+        rest: &str,
+        action: &str,
+        results: Vec<(String, Result<(), String>)>,
+    ) {
+        if let Some((message, kind)) = marks::report(done, rest, action, &results) {
+            self.status.add(status_id, message, kind);
+        }
+    }
+
+    /// The status entry for an action on the given skills.
+    fn skills_status_id(prefix: &str, skills: &[&Skill]) -> String {
+        match skills {
+            [skill] => format!("{}:{}", prefix, skill.name),
+            _ => format!("{}:selection", prefix),
+        }
+    }
+
+    /// Get the components of the selected plugin shown in the current tab.
+    pub fn tab_components(&self) -> Vec<&Component> {
+        match (self.tab, self.selected_plugin()) {
+            (Tab::Components(kind), Some(plugin)) => plugin.components_of(kind).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the currently selected component, if a component tab is shown.
+    pub fn selected_component(&self) -> Option<&Component> {
+        self.tab_components().get(self.selected_component).copied()
+    }
+
+    /// Number of items in the current tab.
+    fn tab_len(&self) -> usize {
+        match self.tab {
+            Tab::Skills => self.selected_plugin().map_or(0, |p| p.skills().len()),
+            Tab::Components(_) => self.tab_components().len(),
+        }
+    }
+
+    /// The selected index in the current tab.
+    fn tab_selection_mut(&mut self) -> &mut usize {
+        match self.tab {
+            Tab::Skills => &mut self.selected_skill,
+            Tab::Components(_) => &mut self.selected_component,
+        }
+    }
+
+    /// Switch to the next tab, or the previous one if `forward` is false.
+    pub fn switch_tab(&mut self, forward: bool) {
+        let tabs = Tab::all();
+        let current = tabs.iter().position(|&t| t == self.tab).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % tabs.len()
+        } else {
+            (current + tabs.len() - 1) % tabs.len()
+        };
+        self.tab = tabs[next];
+        self.selected_component = 0;
+        self.clear_marks();
+        self.select_first_filtered();
+    }
+
     /// Move selection up.
     pub fn select_prev(&mut self) {
         match self.view {
@@ -192,17 +810,25 @@ impl App {
                 }
             }
             View::SkillList => {
-                if self.selected_skill > 0 {
-                    self.selected_skill -= 1;
-                    self.skill_list_state.select(Some(self.selected_skill));
-                }
+                let selection = self.tab_selection_mut();
+                *selection = selection.saturating_sub(1);
+                self.skill_list_state.select(Some(self.selected_skill));
             }
             View::LinkTargetSelect => {
                 if self.link_target_selection > 0 {
                     self.link_target_selection -= 1;
                 }
             }
-            View::InstallInput => {}
+            View::Health => self.health_selection = self.health_selection.saturating_sub(1),
+            View::Available => {
+                self.available_selection = self.available_selection.saturating_sub(1)
+            }
+            View::GlobalSearch => self.global_selection = self.global_selection.saturating_sub(1),
+            View::AllSkills => self.step_all_skills(-1),
+            View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::MarketplaceInput => {}
         }
     }
 
@@ -217,21 +843,38 @@ impl App {
                 }
             }
             View::SkillList => {
-                if let Some(plugin) = self.selected_plugin() {
-                    let skill_count = plugin.skills().len();
-                    if skill_count > 0 && self.selected_skill < skill_count - 1 {
-                        self.selected_skill += 1;
-                        self.skill_list_state.select(Some(self.selected_skill));
-                    }
+                let count = self.tab_len();
+                let selection = self.tab_selection_mut();
+                if *selection + 1 < count {
+                    *selection += 1;
                 }
+                self.skill_list_state.select(Some(self.selected_skill));
             }
             View::LinkTargetSelect => {
-                let target_count = LinkTarget::all().len();
-                if self.link_target_selection < target_count - 1 {
+                if self.link_target_selection + 1 < LinkTarget::all().len() {
                     self.link_target_selection += 1;
                 }
             }
-            View::InstallInput => {}
+            View::Health => {
+                if self.health_selection + 1 < self.health.len() {
+                    self.health_selection += 1;
+                }
+            }
+            View::Available => {
+                if self.available_selection + 1 < self.available_entries().len() {
+                    self.available_selection += 1;
+                }
+            }
+            View::GlobalSearch => {
+                if self.global_selection + 1 < self.global_results.len() {
+                    self.global_selection += 1;
+                }
+            }
+            View::AllSkills => self.step_all_skills(1),
+            View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::MarketplaceInput => {}
         }
     }
 
@@ -247,15 +890,26 @@ impl App {
                 }
             }
             View::SkillList => {
-                if let Some(plugin) = self.selected_plugin() {
-                    let skill_count = plugin.skills().len();
-                    if skill_count > 0 {
-                        self.selected_skill = (self.selected_skill + SCROLL_AMOUNT).min(skill_count - 1);
-                        self.skill_list_state.select(Some(self.selected_skill));
-                    }
+                let count = self.tab_len();
+                if count > 0 {
+                    let selection = self.tab_selection_mut();
+                    *selection = (*selection + SCROLL_AMOUNT).min(count - 1);
+                    self.skill_list_state.select(Some(self.selected_skill));
                 }
             }
-            View::LinkTargetSelect | View::InstallInput => {}
+            View::Available => {
+                let count = self.available_entries().len();
+                self.available_selection =
+                    (self.available_selection + SCROLL_AMOUNT).min(count.saturating_sub(1));
+            }
+            View::AllSkills => self.step_all_skills(SCROLL_AMOUNT as isize),
+            View::LinkTargetSelect
+            | View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::Health
+            | View::MarketplaceInput
+            | View::GlobalSearch => {}
         }
     }
 
@@ -268,21 +922,39 @@ impl App {
                 self.plugin_list_state.select(Some(self.selected_plugin));
             }
             View::SkillList => {
-                self.selected_skill = self.selected_skill.saturating_sub(SCROLL_AMOUNT);
+                let selection = self.tab_selection_mut();
+                *selection = selection.saturating_sub(SCROLL_AMOUNT);
                 self.skill_list_state.select(Some(self.selected_skill));
             }
-            View::LinkTargetSelect | View::InstallInput => {}
+            View::Available => {
+                self.available_selection = self.available_selection.saturating_sub(SCROLL_AMOUNT)
+            }
+            View::AllSkills => self.step_all_skills(-(SCROLL_AMOUNT as isize)),
+            View::LinkTargetSelect
+            | View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::Health
+            | View::MarketplaceInput
+            | View::GlobalSearch => {}
         }
     }
 
     /// Enter skill list view for selected plugin.
     pub fn enter_skill_list(&mut self) {
         if self.is_selected_installing() {
-            self.status.add("view:error", "Plugin is still installing", StatusKind::Error);
+            self.status.add(
+                "view:error",
+                "Plugin is still installing",
+                StatusKind::Error,
+            );
             return;
         }
         if self.selected_plugin().is_some() {
             self.selected_skill = 0;
+            self.selected_component = 0;
+            self.tab = Tab::Skills;
+            self.clear_marks();
             self.skill_list_state.select(Some(0));
             self.view = View::SkillList;
         }
@@ -301,63 +973,359 @@ impl App {
     }
 
     /// Update the currently selected plugin.
+    ///
+    /// Plugins that follow a branch are fetched first and the update is shown
+    /// for review; it is only applied once accepted.
     pub fn update_selected(&mut self) {
         if self.plugins.is_empty() {
-            self.status.add("update:error", "No plugin selected", StatusKind::Error);
+            self.status
+                .add("update:error", "No plugin selected", StatusKind::Error);
             return;
         }
 
         if self.is_selected_installing() {
-            self.status.add("update:error", "Plugin is still installing", StatusKind::Error);
+            self.status.add(
+                "update:error",
+                "Plugin is still installing",
+                StatusKind::Error,
+            );
             return;
         }
 
         let plugin = Arc::clone(&self.plugins[self.selected_plugin]);
         let name = format!("{}/{}", plugin.owner, plugin.name());
         let status_id = format!("update:{}", name);
-        self.status.add(&status_id, format!("Updating {}...", name), StatusKind::Progress);
+        let Some(guard) = self.claim_plugin(&plugin, &status_id) else {
+            return;
+        };
+
+        if !plugin.is_rescan_only() {
+            self.status.add(
+                &status_id,
+                format!("Fetching {}...", name),
+                StatusKind::Progress,
+            );
 
+            let manager = self.manager.clone();
+            let (tx, rx) = std::sync::mpsc::channel();
+            let fetch_plugin = Arc::clone(&plugin);
+
+            std::thread::spawn(move || {
+                let _guard = guard;
+                let result = manager.fetch_update(&fetch_plugin);
+                let _ = tx.send(result);
+            });
+
+            self.fetching.push((plugin, name, rx));
+            return;
+        }
+
+        self.status.add(
+            &status_id,
+            format!("Updating {}...", name),
+            StatusKind::Progress,
+        );
+
+        let manager = self.manager.clone();
         let (tx, rx) = std::sync::mpsc::channel();
         let idx = self.selected_plugin;
 
         std::thread::spawn(move || {
-            let result = plugin.update();
+            let _guard = guard;
+            let result = manager.update(&plugin);
             let _ = tx.send(result);
         });
 
         self.updating.push((idx, name, rx));
     }
 
-    /// Poll for completed background updates.
-    pub fn poll_updates(&mut self) {
+    /// Roll the currently selected plugin back to its previous revision.
+    pub fn rollback_selected(&mut self) {
+        if self.plugins.is_empty() {
+            self.status
+                .add("rollback:error", "No plugin selected", StatusKind::Error);
+            return;
+        }
+
+        if self.is_selected_installing() {
+            self.status.add(
+                "rollback:error",
+                "Plugin is still installing",
+                StatusKind::Error,
+            );
+            return;
+        }
+
+        let plugin = Arc::clone(&self.plugins[self.selected_plugin]);
+        let name = format!("{}/{}", plugin.owner, plugin.name());
+        let status_id = format!("rollback:{}", name);
+        let Some(guard) = self.claim_plugin(&plugin, &status_id) else {
+            return;
+        };
+
+        self.status.add(
+            &status_id,
+            format!("Rolling back {}...", name),
+            StatusKind::Progress,
+        );
+
+        let manager = self.manager.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        // Rolling back may have to fetch the previous commit
+        std::thread::spawn(move || {
+            let result = manager.rollback(&plugin);
+            drop(guard);
+            let _ = tx.send(result);
+        });
+
+        self.rolling_back.push((name, rx));
+    }
+
+    /// Poll for completed background rollbacks.
+    pub fn poll_rollbacks(&mut self) {
         let mut completed = Vec::new();
 
-        for (i, (idx, name, rx)) in self.updating.iter().enumerate() {
+        for (i, (_, rx)) in self.rolling_back.iter().enumerate() {
             if let Ok(result) = rx.try_recv() {
-                completed.push((i, *idx, name.clone(), result));
+                completed.push((i, result));
             }
         }
 
         // Remove completed in reverse order to preserve indices
-        for (i, idx, name, result) in completed.into_iter().rev() {
-            self.updating.remove(i);
-            let status_id = format!("update:{}", name);
+        for (i, result) in completed.into_iter().rev() {
+            let (name, _) = self.rolling_back.remove(i);
+            let status_id = format!("rollback:{}", name);
             match result {
-                Ok(updated_plugin) => {
+                Ok(rolled_back) => {
+                    let commit = rolled_back.head_commit().unwrap_or_default();
+                    self.status.add(
+                        &status_id,
+                        format!(
+                            "Rolled back: {} to {}",
+                            name,
+                            &commit[..commit.len().min(7)]
+                        ),
+                        StatusKind::Success,
+                    );
+                    if let Some(idx) = self.plugins.iter().position(|p| p.path == rolled_back.path)
+                    {
+                        self.plugins[idx] = Arc::new(rolled_back);
+                    }
+                    self.start_update_check();
+                }
+                Err(e) => {
+                    self.status.add(
+                        &status_id,
+                        format!("Rollback failed: {}", e),
+                        StatusKind::Error,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Poll for completed background updates.
+    pub fn poll_updates(&mut self) {
+        let mut completed = Vec::new();
+
+        for (i, (idx, name, rx)) in self.updating.iter().enumerate() {
+            if let Ok(result) = rx.try_recv() {
+                completed.push((i, *idx, name.clone(), result));
+            }
+        }
+
+        // Remove completed in reverse order to preserve indices
+        for (i, idx, name, result) in completed.into_iter().rev() {
+            self.updating.remove(i);
+            let status_id = format!("update:{}", name);
+            match result {
+                Ok(updated_plugin) => {
+                    let message = match &updated_plugin.reference {
+                        _ if updated_plugin.is_local() => format!("Rescanned: {} (local)", name),
+                        Some(reference) if updated_plugin.is_fixed() => {
+                            format!("Rescanned: {} (pinned to {})", name, reference)
+                        }
+                        _ => format!("Updated: {}", name),
+                    };
+                    self.behind.remove(&updated_plugin.path);
                     if idx < self.plugins.len() {
                         self.plugins[idx] = Arc::new(updated_plugin);
                     }
-                    self.status.add(&status_id, format!("Updated: {}", name), StatusKind::Success);
+                    self.status.add(&status_id, message, StatusKind::Success);
                 }
                 Err(e) => {
-                    self.status.add(&status_id, format!("Update failed: {}", e), StatusKind::Error);
+                    self.status.add(
+                        &status_id,
+                        format!("Update failed: {}", e),
+                        StatusKind::Error,
+                    );
                 }
             }
         }
     }
 
-    /// Enter the link target selection view for the currently selected skill.
+    /// Poll for completed background fetches and queue updates for review.
+    pub fn poll_fetches(&mut self) {
+        let mut completed = Vec::new();
+
+        for (i, (_, _, rx)) in self.fetching.iter().enumerate() {
+            if let Ok(result) = rx.try_recv() {
+                completed.push((i, result));
+            }
+        }
+
+        // Remove completed in reverse order to preserve indices
+        for (i, result) in completed.into_iter().rev() {
+            let (plugin, name, _) = self.fetching.remove(i);
+            let status_id = format!("update:{}", name);
+            match result {
+                Ok(pending) if pending.is_up_to_date() => {
+                    self.status.add(
+                        &status_id,
+                        format!("Already up to date: {}", name),
+                        StatusKind::Success,
+                    );
+                }
+                Ok(pending) => {
+                    self.status.add(
+                        &status_id,
+                        format!(
+                            "Review update: {} ({} commits)",
+                            name,
+                            pending.commits.len()
+                        ),
+                        StatusKind::Info,
+                    );
+                    self.reviews.push(UpdateReview {
+                        plugin,
+                        name,
+                        pending,
+                    });
+                }
+                Err(e) => {
+                    self.status.add(
+                        &status_id,
+                        format!("Update failed: {}", e),
+                        StatusKind::Error,
+                    );
+                }
+            }
+        }
+
+        // Only interrupt the user from the plugin list
+        if self.view == View::PluginList && !self.search_active && !self.reviews.is_empty() {
+            self.preview_scroll = 0;
+            self.view = View::UpdatePreview;
+        }
+    }
+
+    /// Get the update currently under review.
+    pub fn current_review(&self) -> Option<&UpdateReview> {
+        self.reviews.first()
+    }
+
+    /// Accept the update under review and apply it in the background.
+    pub fn accept_update(&mut self) {
+        let Some(review) = self.reviews.first() else {
+            return;
+        };
+        // Keep the review until the plugin is free to update
+        let plugin = Arc::clone(&review.plugin);
+        let Some(guard) = self.claim_plugin(&plugin, &format!("update:{}", review.name)) else {
+            return;
+        };
+        let UpdateReview {
+            plugin,
+            name,
+            pending,
+        } = self.reviews.remove(0);
+        self.next_review();
+
+        // The plugin list may have changed while the update was under review
+        let Some(idx) = self.plugins.iter().position(|p| p.path == plugin.path) else {
+            self.status.add(
+                format!("update:{}", name),
+                format!("Update failed: {} was removed", name),
+                StatusKind::Error,
+            );
+            return;
+        };
+
+        self.status.add(
+            format!("update:{}", name),
+            format!("Updating {}...", name),
+            StatusKind::Progress,
+        );
+
+        let manager = self.manager.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let _guard = guard;
+            let result = manager.apply_update(&plugin, &pending);
+            let _ = tx.send(result);
+        });
+
+        self.updating.push((idx, name, rx));
+    }
+
+    /// Reject the update under review, leaving the plugin as it is.
+    pub fn reject_update(&mut self) {
+        if self.reviews.is_empty() {
+            return;
+        }
+        let review = self.reviews.remove(0);
+        self.status.add(
+            format!("update:{}", review.name),
+            format!("Rejected update: {}", review.name),
+            StatusKind::Info,
+        );
+        self.next_review();
+    }
+
+    /// Show the next queued update, or return to the plugin list.
+    fn next_review(&mut self) {
+        self.preview_scroll = 0;
+        if self.reviews.is_empty() {
+            self.view = View::PluginList;
+        }
+    }
+
+    /// Scroll the update preview by `delta` lines.
+    pub fn scroll_preview(&mut self, delta: i16) {
+        self.preview_scroll = self.preview_scroll.saturating_add_signed(delta);
+    }
+
+    /// Enter the link target selection view for the currently selected skill,
+    /// or command or agent.
     pub fn enter_link_target_view(&mut self) {
+        if LinkTarget::all().is_empty() {
+            self.status
+                .add("link", "No link targets configured", StatusKind::Error);
+            return;
+        }
+
+        if let Tab::Components(kind) = self.tab {
+            match self.selected_component() {
+                Some(component) if !kind.is_linkable() => {
+                    let message =
+                        format!("{} are configuration and cannot be linked", kind.label());
+                    self.status.add(
+                        format!("link:{}", component.name),
+                        message,
+                        StatusKind::Info,
+                    );
+                }
+                Some(_) => {
+                    self.link_target_selection = 0;
+                    self.view = View::LinkTargetSelect;
+                }
+                None => {}
+            }
+            return;
+        }
+
         let Some(plugin) = self.selected_plugin() else {
             return;
         };
@@ -372,41 +1340,226 @@ impl App {
 
     /// Toggle link/unlink for the currently selected link target.
     pub fn toggle_selected_link_target(&mut self) {
-        let Some(plugin) = self.selected_plugin() else {
-            return;
-        };
-        let skills = plugin.skills();
-        if skills.is_empty() || self.selected_skill >= skills.len() {
+        if self.tab != Tab::Skills {
+            let Some(component) = self.selected_component() else {
+                return;
+            };
+            let Some(target) = LinkTarget::all().get(self.link_target_selection) else {
+                return;
+            };
+            let status_id = format!("link:{}:{}", target.display_name(), component.name);
+            let (result, message) = if component.is_linked_to(target) {
+                let message = format!("Unlinked {} from {}", component.name, target.display_name());
+                (component.unlink_from(target), message)
+            } else {
+                let message = format!("Linked {} to {}", component.name, target.display_name());
+                (component.link_to(target), message)
+            };
+            match result {
+                Ok(()) => self.status.add(&status_id, message, StatusKind::Success),
+                Err(e) => {
+                    self.status
+                        .add(&status_id, format!("Link failed: {}", e), StatusKind::Error)
+                }
+            }
             return;
         }
 
-        let targets = LinkTarget::all();
-        if self.link_target_selection >= targets.len() {
+        let skills = self.selected_skills();
+        let Some(target) = LinkTarget::all().get(self.link_target_selection) else {
             return;
+        };
+        let status_id = Self::skills_status_id(&format!("link:{}", target.display_name()), &skills);
+
+        // Unlink only when every skill is linked, so a mixed selection ends up linked
+        let all_linked = skills.iter().all(|s| s.is_linked_to(target));
+        let results: Vec<(String, Result<(), String>)> = skills
+            .iter()
+            .filter(|s| s.is_linked_to(target) == all_linked)
+            .map(|s| {
+                let result = if all_linked {
+                    unlink_unedited(s, target)
+                } else {
+                    s.link_to(target)
+                };
+                (s.name.clone(), result.map_err(|e| e.to_string()))
+            })
+            .collect();
+
+        let target = format!(
+            "{} {}",
+            if all_linked { "from" } else { "to" },
+            target.display_name()
+        );
+        match all_linked {
+            true => self.report_skills(status_id, "Unlinked", &target, "Unlink", results),
+            false => self.report_skills(status_id, "Linked", &target, "Link", results),
         }
+    }
 
-        let target = targets[self.link_target_selection];
-        let skill = &skills[self.selected_skill];
-        let status_id = format!("link:{}:{}", target.display_name(), skill.name);
+    /// Unlink the current or marked skills from the selected link target.
+    pub fn unlink_from_selected_target(&mut self) {
+        let Some(target) = LinkTarget::all().get(self.link_target_selection) else {
+            return;
+        };
+        let status_id = format!("link:{}", target.display_name());
+        let rest = format!("from {}", target.display_name());
+        self.unlink_selected(std::slice::from_ref(target), &status_id, &rest);
+    }
 
-        if skill.is_linked_to(target) {
-            match skill.unlink_from(target) {
-                Ok(()) => self.status.add(
-                    &status_id,
-                    format!("Unlinked {} from {}", skill.name, target.display_name()),
-                    StatusKind::Success,
-                ),
-                Err(e) => self.status.add(&status_id, format!("Unlink failed: {}", e), StatusKind::Error),
-            }
-        } else {
-            match skill.link_to(target) {
-                Ok(()) => self.status.add(
+    /// Unlink the current or marked skills from every target.
+    pub fn unlink_from_all_targets(&mut self) {
+        self.unlink_selected(LinkTarget::all(), "link:all", "from all targets");
+    }
+
+    /// Unlink the current component, or the current or marked skills, from
+    /// the given targets they are linked to.
+    fn unlink_selected(&mut self, targets: &[LinkTarget], status_prefix: &str, rest: &str) {
+        if self.tab != Tab::Skills {
+            let Some(component) = self.selected_component() else {
+                return;
+            };
+            let status_id = format!("{}:{}", status_prefix, component.name);
+            let result = targets
+                .iter()
+                .filter(|t| component.is_linked_to(t))
+                .try_for_each(|t| component.unlink_from(t));
+            match result {
+                Ok(()) => {
+                    let message = format!("Unlinked {} {}", component.name, rest);
+                    self.status.add(&status_id, message, StatusKind::Success);
+                }
+                Err(e) => self.status.add(
                     &status_id,
-                    format!("Linked {} to {}", skill.name, target.display_name()),
-                    StatusKind::Success,
+                    format!("Unlink failed: {}", e),
+                    StatusKind::Error,
                 ),
-                Err(e) => self.status.add(&status_id, format!("Link failed: {}", e), StatusKind::Error),
             }
+            return;
+        }
+
+        let skills = self.selected_skills();
+        let status_id = Self::skills_status_id(status_prefix, &skills);
+        let results: Vec<(String, Result<(), String>)> = skills
+            .iter()
+            .filter(|s| targets.iter().any(|t| s.is_linked_to(t)))
+            .map(|skill| {
+                let result = targets
+                    .iter()
+                    .filter(|t| skill.is_linked_to(t))
+                    .try_for_each(|target| {
+                        unlink_unedited(skill, target).map_err(|e| match targets {
+                            [_] => e.to_string(),
+                            _ => format!("{}: {}", target.display_name(), e),
+                        })
+                    });
+                (skill.name.clone(), result)
+            })
+            .collect();
+
+        if results.is_empty() {
+            let message = match skills.as_slice() {
+                [skill] => format!("{} is not linked", skill.name),
+                _ => "None of the marked skills are linked".to_string(),
+            };
+            self.status.add(&status_id, message, StatusKind::Info);
+            return;
+        }
+        self.report_skills(status_id, "Unlinked", rest, "Unlink", results);
+    }
+
+    /// Copy the current skill into the selected link target.
+    ///
+    /// An existing copy is replaced, unless it was edited locally; a symlink is left alone.
+    pub fn copy_to_selected_target(&mut self) {
+        if let Some(component) = self.selected_component() {
+            let message = if component.kind.is_linkable() {
+                format!("{} can only be symlinked", component.kind.label())
+            } else {
+                format!("{} cannot be linked", component.kind.label())
+            };
+            self.status.add(
+                format!("link:{}", component.name),
+                message,
+                StatusKind::Info,
+            );
+            return;
+        }
+        let skills = self.selected_skills();
+        let Some(target) = LinkTarget::all().get(self.link_target_selection) else {
+            return;
+        };
+        let status_id = Self::skills_status_id(&format!("link:{}", target.display_name()), &skills);
+
+        let results = skills
+            .iter()
+            .map(|skill| {
+                let result = match skill.link_status(target) {
+                    LinkStatus::Symlinked => Err(PluginError::AlreadyLinked {
+                        name: skill.qualified_name(),
+                    }),
+                    LinkStatus::NotLinked => skill.link_to_with(target, LinkMode::Copy),
+                    LinkStatus::Copied | LinkStatus::Outdated | LinkStatus::Modified => {
+                        unlink_unedited(skill, target)
+                            .and_then(|()| skill.link_to_with(target, LinkMode::Copy))
+                    }
+                };
+                (skill.name.clone(), result.map_err(|e| e.to_string()))
+            })
+            .collect();
+
+        let target = format!("to {}", target.display_name());
+        self.report_skills(status_id, "Copied", &target, "Copy", results);
+    }
+
+    /// Show the metadata of the currently selected skill.
+    pub fn enter_skill_detail(&mut self) {
+        if self.selected_skill().is_some() {
+            self.view = View::SkillDetail;
+        }
+    }
+
+    /// Show or hide the SKILL.md preview pane.
+    pub fn toggle_skill_preview(&mut self) {
+        self.show_skill_preview = !self.show_skill_preview;
+    }
+
+    /// Load the selected skill into the preview pane.
+    ///
+    /// The file is only read again when the selection changes or the file
+    /// is modified, so this is cheap to call on every frame.
+    pub fn load_skill_preview(&mut self) -> Option<&mut SkillPreview> {
+        let skill = self.selected_skill()?;
+        let modified = fs::metadata(&skill.path).and_then(|m| m.modified()).ok();
+
+        let stale = self
+            .skill_preview
+            .as_ref()
+            .is_none_or(|p| p.path != skill.path || p.modified != modified);
+        if stale {
+            let content = fs::read_to_string(&skill.path)
+                .unwrap_or_else(|e| format!("Cannot read SKILL.md: {}", e));
+            let files = skill.files().unwrap_or_default();
+            // Keep the scroll position when the same file changes on disk
+            let scroll = match &self.skill_preview {
+                Some(p) if p.path == skill.path => p.scroll,
+                _ => 0,
+            };
+            self.skill_preview = Some(SkillPreview {
+                path: skill.path.clone(),
+                modified,
+                content,
+                files,
+                scroll,
+            });
+        }
+        self.skill_preview.as_mut()
+    }
+
+    /// Scroll the SKILL.md preview by `delta` lines.
+    pub fn scroll_skill_preview(&mut self, delta: i16) {
+        if let Some(preview) = &mut self.skill_preview {
+            preview.scroll = preview.scroll.saturating_add_signed(delta);
         }
     }
 
@@ -417,60 +1570,657 @@ impl App {
 
     /// Link or unlink the currently selected skill to/from all targets.
     /// If any target is not linked, links to all. If all are linked, unlinks from all.
+    ///
+    /// Commands and agents are linked to the targets that take them.
     pub fn link_to_all_targets(&mut self) {
-        let Some(plugin) = self.selected_plugin() else {
+        if self.tab != Tab::Skills {
+            let Some(component) = self.selected_component() else {
+                return;
+            };
+            let status_id = format!("link:all:{}", component.name);
+            let targets: Vec<&LinkTarget> = LinkTarget::all()
+                .iter()
+                .filter(|t| component.link_path_for(t).is_some())
+                .collect();
+            if targets.is_empty() {
+                self.status.add(
+                    &status_id,
+                    format!("{} cannot be linked", component.name),
+                    StatusKind::Info,
+                );
+                return;
+            }
+
+            let all_linked = targets.iter().all(|t| component.is_linked_to(t));
+            let result =
+                targets
+                    .iter()
+                    .try_for_each(|t| match (all_linked, component.is_linked_to(t)) {
+                        (true, _) => component.unlink_from(t),
+                        (false, false) => component.link_to(t),
+                        (false, true) => Ok(()),
+                    });
+            match result {
+                Ok(()) if all_linked => {
+                    let message = format!("Unlinked {} from all targets", component.name);
+                    self.status.add(&status_id, message, StatusKind::Success);
+                }
+                Ok(()) => {
+                    let message = format!("Linked {} to all targets", component.name);
+                    self.status.add(&status_id, message, StatusKind::Success);
+                }
+                Err(e) => {
+                    self.status
+                        .add(&status_id, format!("Link failed: {}", e), StatusKind::Error)
+                }
+            }
+            return;
+        }
+
+        let skills = self.selected_skills();
+        let targets = LinkTarget::all();
+        let status_id = Self::skills_status_id("link:all", &skills);
+
+        // Unlink only when every skill is linked everywhere; otherwise link to every
+        // unlinked target
+        let all_linked = skills
+            .iter()
+            .all(|s| targets.iter().all(|t| s.is_linked_to(t)));
+        let results = skills
+            .iter()
+            .map(|skill| {
+                let result = targets.iter().try_for_each(|target| {
+                    let result = match (all_linked, skill.is_linked_to(target)) {
+                        (true, _) => unlink_unedited(skill, target),
+                        (false, false) => skill.link_to(target),
+                        (false, true) => Ok(()),
+                    };
+                    result.map_err(|e| format!("{}: {}", target.display_name(), e))
+                });
+                (skill.name.clone(), result)
+            })
+            .collect();
+
+        match all_linked {
+            true => {
+                self.report_skills(status_id, "Unlinked", "from all targets", "Unlink", results)
+            }
+            false => self.report_skills(status_id, "Linked", "to all targets", "Link", results),
+        }
+    }
+
+    /// Scan the link targets and show their health.
+    pub fn enter_health_view(&mut self) {
+        self.scan_health();
+        self.health_selection = 0;
+        self.view = View::Health;
+    }
+
+    /// Scan the link targets again, listing problems before healthy entries.
+    pub fn scan_health(&mut self) {
+        match self.manager.doctor() {
+            Ok(mut entries) => {
+                entries.sort_by_key(|e| e.health == Health::Healthy);
+                self.health = entries;
+                self.health_selection = self
+                    .health_selection
+                    .min(self.health.len().saturating_sub(1));
+            }
+            Err(e) => self
+                .status
+                .add("doctor", format!("Scan failed: {}", e), StatusKind::Error),
+        }
+    }
+
+    /// Get the currently selected health entry.
+    pub fn selected_health_entry(&self) -> Option<&LinkEntry> {
+        self.health.get(self.health_selection)
+    }
+
+    /// Repair the selected entry in the background, fixing broken links if no
+    /// repair is given.
+    ///
+    /// Other entries are only repaired when a repair is chosen, since they
+    /// may be skills written by hand.
+    pub fn repair_selected(&mut self, repair: Option<Repair>) {
+        let Some(entry) = self.selected_health_entry().cloned() else {
             return;
         };
-        let skills = plugin.skills();
-        if skills.is_empty() || self.selected_skill >= skills.len() {
+        let status_id = format!("doctor:{}", entry.path.display());
+        let Some(repair) = repair.or_else(|| entry.fix()) else {
+            let message = match entry.repairs().as_slice() {
+                [] => format!("Nothing to repair for {}", entry.name()),
+                repairs => {
+                    let repairs: Vec<&str> = repairs.iter().map(|r| r.id()).collect();
+                    format!(
+                        "Choose a repair for {}: {}",
+                        entry.name(),
+                        repairs.join(", ")
+                    )
+                }
+            };
+            self.status.add(&status_id, message, StatusKind::Info);
+            return;
+        };
+
+        let label = format!("Repairing {} ({})", entry.name(), repair.id());
+        self.spawn_repair(status_id, label, move |manager| {
+            match manager.repair(&entry, repair) {
+                Ok(()) => Ok(format!("Repaired {} ({})", entry.name(), repair.id())),
+                Err(e) => Err(format!("Repair failed: {}", e)),
+            }
+        });
+    }
+
+    /// Take over every unmanaged entry in the background: replace those whose
+    /// origin is known with links, and register the rest as local plugins.
+    pub fn adopt_all(&mut self) {
+        let unmanaged: Vec<LinkEntry> = self
+            .health
+            .iter()
+            .filter(|e| e.health == Health::Unmanaged && !e.repairs().is_empty())
+            .cloned()
+            .collect();
+        if unmanaged.is_empty() {
+            self.status.add(
+                "doctor:adopt",
+                "No unmanaged skills to adopt",
+                StatusKind::Info,
+            );
             return;
         }
 
-        let skill = &skills[self.selected_skill];
-        let targets = LinkTarget::all();
+        let label = format!("Adopting {} unmanaged skills", unmanaged.len());
+        self.spawn_repair("doctor:adopt".to_string(), label, move |manager| {
+            let failures: Vec<String> = unmanaged
+                .iter()
+                .filter_map(|entry| {
+                    let result = manager.repair(entry, entry.repairs()[0]);
+                    result.err().map(|e| format!("{}: {}", entry.name(), e))
+                })
+                .collect();
+            match failures.as_slice() {
+                [] => Ok(format!("Adopted {} unmanaged skills", unmanaged.len())),
+                _ => Err(format!("Adopt failed for {}", failures.join(", "))),
+            }
+        });
+    }
+
+    /// Run repairs on a background thread, showing `label` until they finish.
+    ///
+    /// Replacing a clone can install its plugin, so repairs never run on the
+    /// UI thread; only one runs at a time.
+    fn spawn_repair<F>(&mut self, status_id: String, label: String, task: F)
+    where
+        F: FnOnce(&PluginManager) -> Result<String, String> + Send + 'static,
+    {
+        if self.repairing.is_some() {
+            self.status.add(
+                &status_id,
+                "Another repair is still running",
+                StatusKind::Error,
+            );
+            return;
+        }
+        self.status
+            .add(&status_id, format!("{}...", label), StatusKind::Progress);
+
+        let manager = self.manager.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(task(&manager));
+        });
+        self.repairing = Some((status_id, rx));
+    }
+
+    /// Poll for a finished repair, then reload the plugins and scan the link targets again.
+    pub fn poll_repairs(&mut self) {
+        let Some((status_id, rx)) = &self.repairing else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("Repair stopped unexpectedly".to_string()),
+        };
+        let status_id = status_id.clone();
+        self.repairing = None;
+
+        match result {
+            Ok(message) => self.status.add(&status_id, message, StatusKind::Success),
+            Err(message) => self.status.add(&status_id, message, StatusKind::Error),
+        }
+        // Adopting and replacing can register or install plugins
+        if let Ok(plugins) = self.manager.list_installed() {
+            self.plugins = plugins;
+        }
+        self.scan_health();
+    }
+
+    /// Show the plugins the added marketplaces offer.
+    pub fn enter_available_view(&mut self) {
+        self.load_marketplaces();
+        self.exit_search();
+        self.view = View::Available;
+    }
+
+    /// Read the added marketplaces again.
+    pub fn load_marketplaces(&mut self) {
+        let (marketplaces, errors) = self.manager.list_marketplaces();
+        self.marketplaces = marketplaces;
+        self.available_selection = self
+            .available_selection
+            .min(self.available_entries().len().saturating_sub(1));
+        self.check_installed_entries();
+        if !errors.is_empty() {
+            let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            self.status.add(
+                "marketplace",
+                format!("Skipped {}", errors.join(", ")),
+                StatusKind::Error,
+            );
+        }
+    }
+
+    /// Check which marketplace entries are installed.
+    fn check_installed_entries(&mut self) {
+        let manager = &self.manager;
+        self.installed_entries = self
+            .marketplaces
+            .iter()
+            .flat_map(|m| {
+                m.plugins
+                    .iter()
+                    .filter(|e| manager.is_entry_installed(m, e))
+                    .map(|e| (m.name.clone(), e.name.clone()))
+            })
+            .collect();
+    }
+
+    /// Check if a marketplace entry was installed when last checked.
+    pub fn is_entry_installed(&self, marketplace: &Marketplace, entry: &MarketplaceEntry) -> bool {
+        self.installed_entries
+            .contains(&(marketplace.name.clone(), entry.name.clone()))
+    }
+
+    /// Get the entries of every marketplace, in marketplace order.
+    pub fn available_entries(&self) -> Vec<(&Marketplace, &MarketplaceEntry)> {
+        self.marketplaces
+            .iter()
+            .flat_map(|m| m.plugins.iter().map(move |e| (m, e)))
+            .collect()
+    }
+
+    /// Get filtered indices into the available entries, by name and description.
+    pub fn filtered_available_indices(&self) -> Vec<usize> {
+        let query = self.search_query.to_lowercase();
+        self.available_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, entry))| {
+                entry.name.to_lowercase().contains(&query)
+                    || entry
+                        .description
+                        .as_ref()
+                        .is_some_and(|d| d.to_lowercase().contains(&query))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Get the currently selected marketplace entry.
+    pub fn selected_available_entry(&self) -> Option<(&Marketplace, &MarketplaceEntry)> {
+        self.available_entries()
+            .get(self.available_selection)
+            .copied()
+    }
+
+    /// Check if a marketplace entry is being installed.
+    pub fn is_entry_installing(&self, marketplace: &Marketplace, entry: &MarketplaceEntry) -> bool {
+        marketplace
+            .install_url(entry)
+            .is_ok_and(|url| self.installing.iter().any(|(u, _)| *u == url))
+    }
+
+    /// Install the selected marketplace entry in the background.
+    pub fn install_selected_entry(&mut self) {
+        let Some((marketplace, entry)) = self.selected_available_entry() else {
+            return;
+        };
+        match marketplace.install_url(entry) {
+            Ok(url) => {
+                self.install_url(url);
+            }
+            Err(e) => self
+                .status
+                .add("install:error", e.to_string(), StatusKind::Error),
+        }
+    }
+
+    /// Enter the marketplace input view.
+    pub fn enter_marketplace_input(&mut self) {
+        self.input.clear();
+        self.view = View::MarketplaceInput;
+    }
+
+    /// Go back to the Available view.
+    pub fn back_to_available(&mut self) {
+        self.view = View::Available;
+        self.input.clear();
+    }
+
+    /// Start adding a marketplace from the current input in the background.
+    pub fn start_add_marketplace(&mut self) {
+        let source = self.input.trim().to_string();
+        if source.is_empty() {
+            self.status.add(
+                "marketplace:error",
+                "Source cannot be empty",
+                StatusKind::Error,
+            );
+            return;
+        }
+        self.back_to_available();
+
+        let manager = self.manager.clone();
+        self.spawn_marketplace_task(format!("Adding {}", source), move || {
+            let marketplace = manager.add_marketplace(&source)?;
+            Ok(format!(
+                "Added {} ({} plugins)",
+                marketplace.name,
+                marketplace.plugins.len()
+            ))
+        });
+    }
+
+    /// Update every added marketplace in the background.
+    pub fn update_marketplaces(&mut self) {
+        if self.marketplaces.is_empty() {
+            self.status.add(
+                "marketplace:update",
+                "No marketplaces added",
+                StatusKind::Info,
+            );
+            return;
+        }
+
+        let manager = self.manager.clone();
+        let marketplaces = self.marketplaces.clone();
+        self.spawn_marketplace_task("Updating marketplaces".to_string(), move || {
+            for marketplace in &marketplaces {
+                manager.update_marketplace(marketplace)?;
+            }
+            Ok(format!("Updated {} marketplaces", marketplaces.len()))
+        });
+    }
+
+    /// Run a marketplace operation on a background thread, showing `label` until it finishes.
+    fn spawn_marketplace_task<F>(&mut self, label: String, task: F)
+    where
+        F: FnOnce() -> Result<String, PluginError> + Send + 'static,
+    {
+        let status_id = format!("marketplace:{}", label);
+        self.status
+            .add(&status_id, format!("{}...", label), StatusKind::Progress);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(task());
+        });
+        self.marketplace_tasks.push((status_id, rx));
+    }
+
+    /// Poll for finished marketplace operations, reloading the marketplaces after each.
+    pub fn poll_marketplaces(&mut self) {
+        let mut finished = Vec::new();
+        self.marketplace_tasks
+            .retain(|(status_id, rx)| match rx.try_recv() {
+                Ok(result) => {
+                    finished.push((status_id.clone(), result));
+                    false
+                }
+                Err(TryRecvError::Empty) => true,
+                Err(TryRecvError::Disconnected) => false,
+            });
+
+        for (status_id, result) in finished {
+            match result {
+                Ok(message) => self.status.add(&status_id, message, StatusKind::Success),
+                Err(e) => self
+                    .status
+                    .add(&status_id, format!("Error: {}", e), StatusKind::Error),
+            }
+            self.load_marketplaces();
+        }
+    }
 
-        // Check if all targets are linked
-        let all_linked = targets.iter().all(|t| skill.is_linked_to(*t));
+    /// Index installed skills and marketplace entries, and open global search.
+    pub fn enter_global_search(&mut self) {
+        self.exit_search();
+        self.load_marketplaces();
+        self.search_index = SearchIndex::build(&self.plugins, &self.marketplaces, |m, e| {
+            self.is_entry_installed(m, e)
+        });
+        self.global_query.clear();
+        self.global_results.clear();
+        self.global_selection = 0;
+        self.view = View::GlobalSearch;
+    }
 
-        if all_linked {
-            // Unlink from all
-            for target in targets {
-                if let Err(e) = skill.unlink_from(*target) {
+    /// Add a character to the global search query.
+    pub fn global_search_input(&mut self, c: char) {
+        self.global_query.push(c);
+        self.run_global_search();
+    }
+
+    /// Remove the last character from the global search query.
+    pub fn global_search_backspace(&mut self) {
+        self.global_query.pop();
+        self.run_global_search();
+    }
+
+    /// Search the index for the global query, selecting the best result.
+    fn run_global_search(&mut self) {
+        self.global_results = self.search_index.search(&self.global_query);
+        self.global_selection = 0;
+    }
+
+    /// Show the selected global search result where it lives: an installed
+    /// skill in its plugin's skill list, an available one in the Available view.
+    pub fn open_global_result(&mut self) {
+        let Some(result) = self.global_results.get(self.global_selection) else {
+            return;
+        };
+        match self.search_index.doc(result.doc).source.clone() {
+            SearchSource::Installed { qualified_name, .. } => {
+                let found = self.plugins.iter().enumerate().find_map(|(p, plugin)| {
+                    let s = plugin
+                        .skills()
+                        .iter()
+                        .position(|s| s.qualified_name() == qualified_name)?;
+                    Some((p, s))
+                });
+                let Some((plugin, skill)) = found else {
                     self.status.add(
-                        format!("link:all:{}", skill.name),
-                        format!("Unlink from {} failed: {}", target.display_name(), e),
+                        "search",
+                        format!("{} is no longer installed", qualified_name),
                         StatusKind::Error,
                     );
                     return;
-                }
+                };
+                self.selected_plugin = plugin;
+                self.plugin_list_state.select(Some(plugin));
+                self.enter_skill_list();
+                self.selected_skill = skill;
+                self.skill_list_state.select(Some(skill));
             }
-            self.status.add(
-                format!("link:all:{}", skill.name),
-                format!("Unlinked {} from all targets", skill.name),
-                StatusKind::Success,
-            );
-        } else {
-            // Link to all unlinked targets
-            for target in targets {
-                if !skill.is_linked_to(*target) {
-                    if let Err(e) = skill.link_to(*target) {
-                        self.status.add(
-                            format!("link:all:{}", skill.name),
-                            format!("Link to {} failed: {}", target.display_name(), e),
-                            StatusKind::Error,
-                        );
-                        return;
-                    }
+            SearchSource::Available { marketplace, entry } => {
+                self.enter_available_view();
+                if let Some(i) = self
+                    .available_entries()
+                    .iter()
+                    .position(|(m, e)| m.name == marketplace && e.name == entry)
+                {
+                    self.available_selection = i;
                 }
             }
-            self.status.add(
-                format!("link:all:{}", skill.name),
-                format!("Linked {} to all targets", skill.name),
-                StatusKind::Success,
-            );
         }
     }
 
+    /// Show every skill of every plugin.
+    pub fn enter_all_skills_view(&mut self) {
+        self.exit_search();
+        self.all_skills_selection = 0;
+        self.view = View::AllSkills;
+        self.select_first_filtered();
+    }
+
+    /// Get every skill of every installed plugin, in plugin order.
+    pub fn all_skills(&self) -> Vec<(&Arc<Plugin>, &Skill)> {
+        self.plugins
+            .iter()
+            .flat_map(|p| p.skills().iter().map(move |s| (p, s)))
+            .collect()
+    }
+
+    /// Get the targets the all-skills filter and actions apply to.
+    pub fn all_skills_targets(&self) -> Vec<&'static LinkTarget> {
+        let targets = LinkTarget::all();
+        match self.all_skills_target.and_then(|i| targets.get(i)) {
+            Some(target) => vec![target],
+            None => targets.iter().collect(),
+        }
+    }
+
+    /// Get indices into [`App::all_skills`] passing the filter and the search query.
+    pub fn filtered_all_skills_indices(&self) -> Vec<usize> {
+        let targets = self.all_skills_targets();
+        let query = self.search_query.to_lowercase();
+        self.all_skills()
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, skill))| {
+                let name = skill.qualified_name().to_lowercase();
+                let description = skill.description().unwrap_or_default().to_lowercase();
+                (name.contains(&query) || description.contains(&query))
+                    && self.all_skills_filter.matches(skill, &targets)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Get the skill selected in the all-skills view, if it passes the filter.
+    pub fn selected_all_skill(&self) -> Option<&Skill> {
+        if !self
+            .filtered_all_skills_indices()
+            .contains(&self.all_skills_selection)
+        {
+            return None;
+        }
+        self.all_skills()
+            .get(self.all_skills_selection)
+            .map(|(_, s)| *s)
+    }
+
+    /// Move the all-skills selection by `delta` rows of the filtered list.
+    ///
+    /// A selection that no longer passes the filter moves to the nearest row after it.
+    fn step_all_skills(&mut self, delta: isize) {
+        let filtered = self.filtered_all_skills_indices();
+        if let Some(selection) = filter::step(&filtered, self.all_skills_selection, delta) {
+            self.all_skills_selection = selection;
+        }
+    }
+
+    /// Show the next link state filter in the all-skills view.
+    pub fn cycle_all_skills_filter(&mut self) {
+        self.all_skills_filter = self.all_skills_filter.next();
+        self.step_all_skills(0);
+    }
+
+    /// Narrow the all-skills view to the next link target, then back to all targets.
+    pub fn cycle_all_skills_target(&mut self) {
+        self.all_skills_target = match self.all_skills_target {
+            None if !LinkTarget::all().is_empty() => Some(0),
+            Some(i) if i + 1 < LinkTarget::all().len() => Some(i + 1),
+            _ => None,
+        };
+        self.step_all_skills(0);
+    }
+
+    /// Link the skill selected in the all-skills view to the targets in view.
+    ///
+    /// Broken links in its place are replaced.
+    pub fn link_selected_all_skill(&mut self) {
+        let Some(skill) = self.selected_all_skill() else {
+            return;
+        };
+        let targets = self.all_skills_targets();
+        let status_id = format!("link:{}", skill.qualified_name());
+        if targets.iter().all(|t| skill.is_linked_to(t)) {
+            let message = format!("{} is already linked", skill.name);
+            self.status.add(&status_id, message, StatusKind::Info);
+            return;
+        }
+
+        let result = targets
+            .iter()
+            .filter(|t| !skill.is_linked_to(t))
+            .try_for_each(|target| {
+                let result = match skill.is_link_broken(target) {
+                    true => skill
+                        .unlink_from(target)
+                        .and_then(|()| skill.link_to(target)),
+                    false => skill.link_to(target),
+                };
+                result.map_err(|e| match targets.as_slice() {
+                    [_] => e.to_string(),
+                    _ => format!("{}: {}", target.display_name(), e),
+                })
+            });
+        let rest = match targets.as_slice() {
+            [target] => format!("to {}", target.display_name()),
+            _ => "to all targets".to_string(),
+        };
+        let results = vec![(skill.name.clone(), result)];
+        self.report_skills(status_id, "Linked", &rest, "Link", results);
+        self.step_all_skills(0);
+    }
+
+    /// Unlink the skill selected in the all-skills view from the targets in
+    /// view, removing broken links too.
+    pub fn unlink_selected_all_skill(&mut self) {
+        let Some(skill) = self.selected_all_skill() else {
+            return;
+        };
+        let targets = self.all_skills_targets();
+        let status_id = format!("link:{}", skill.qualified_name());
+        let linked: Vec<&LinkTarget> = targets
+            .iter()
+            .copied()
+            .filter(|t| skill.is_linked_to(t) || skill.is_link_broken(t))
+            .collect();
+        if linked.is_empty() {
+            let message = format!("{} is not linked", skill.name);
+            self.status.add(&status_id, message, StatusKind::Info);
+            return;
+        }
+
+        let result = linked.iter().try_for_each(|target| {
+            unlink_unedited(skill, target).map_err(|e| match targets.as_slice() {
+                [_] => e.to_string(),
+                _ => format!("{}: {}", target.display_name(), e),
+            })
+        });
+        let rest = match targets.as_slice() {
+            [target] => format!("from {}", target.display_name()),
+            _ => "from all targets".to_string(),
+        };
+        let results = vec![(skill.name.clone(), result)];
+        self.report_skills(status_id, "Unlinked", &rest, "Unlink", results);
+        self.step_all_skills(0);
+    }
+
     /// Enter search mode.
     pub fn enter_search(&mut self) {
         self.search_active = true;
@@ -506,13 +2256,29 @@ impl App {
                 }
             }
             View::SkillList => {
-                let filtered = self.filtered_skill_indices();
+                let filtered = self.filtered_tab_indices();
                 if let Some(&first) = filtered.first() {
-                    self.selected_skill = first;
-                    self.skill_list_state.select(Some(first));
+                    *self.tab_selection_mut() = first;
+                    self.skill_list_state.select(Some(self.selected_skill));
                 }
             }
-            View::LinkTargetSelect | View::InstallInput => {}
+            View::Available => {
+                if let Some(&first) = self.filtered_available_indices().first() {
+                    self.available_selection = first;
+                }
+            }
+            View::AllSkills => {
+                if let Some(&first) = self.filtered_all_skills_indices().first() {
+                    self.all_skills_selection = first;
+                }
+            }
+            View::LinkTargetSelect
+            | View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::Health
+            | View::MarketplaceInput
+            | View::GlobalSearch => {}
         }
     }
 
@@ -554,6 +2320,25 @@ impl App {
             .collect()
     }
 
+    /// Get filtered indices into the current tab's components matching the search query.
+    pub fn filtered_component_indices(&self) -> Vec<usize> {
+        let query = self.search_query.to_lowercase();
+        self.tab_components()
+            .iter()
+            .enumerate()
+            .filter(|(_, component)| component.name.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Get filtered indices into the items of the current tab.
+    fn filtered_tab_indices(&self) -> Vec<usize> {
+        match self.tab {
+            Tab::Skills => self.filtered_skill_indices(),
+            Tab::Components(_) => self.filtered_component_indices(),
+        }
+    }
+
     /// Move selection up in filtered results.
     pub fn select_prev_filtered(&mut self) {
         match self.view {
@@ -574,22 +2359,44 @@ impl App {
                 }
             }
             View::SkillList => {
-                let filtered = self.filtered_skill_indices();
+                let filtered = self.filtered_tab_indices();
                 if filtered.is_empty() {
                     return;
                 }
-                let current_pos = filtered.iter().position(|&i| i == self.selected_skill);
+                let selection = self.tab_selection_mut();
+                let current_pos = filtered.iter().position(|i| i == selection);
                 if let Some(pos) = current_pos {
                     if pos > 0 {
-                        self.selected_skill = filtered[pos - 1];
-                        self.skill_list_state.select(Some(self.selected_skill));
+                        *selection = filtered[pos - 1];
                     }
-                } else if !filtered.is_empty() {
-                    self.selected_skill = filtered[0];
-                    self.skill_list_state.select(Some(self.selected_skill));
+                } else {
+                    *selection = filtered[0];
                 }
+                self.skill_list_state.select(Some(self.selected_skill));
             }
-            View::LinkTargetSelect | View::InstallInput => {}
+            View::AllSkills => self.step_all_skills(-1),
+            View::Available => {
+                let filtered = self.filtered_available_indices();
+                match filtered.iter().position(|&i| i == self.available_selection) {
+                    Some(pos) => {
+                        if let Some(&next) = pos.checked_sub(1).and_then(|p| filtered.get(p)) {
+                            self.available_selection = next;
+                        }
+                    }
+                    None => {
+                        if let Some(&first) = filtered.first() {
+                            self.available_selection = first;
+                        }
+                    }
+                }
+            }
+            View::LinkTargetSelect
+            | View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::Health
+            | View::MarketplaceInput
+            | View::GlobalSearch => {}
         }
     }
 
@@ -613,22 +2420,55 @@ impl App {
                 }
             }
             View::SkillList => {
-                let filtered = self.filtered_skill_indices();
+                let filtered = self.filtered_tab_indices();
                 if filtered.is_empty() {
                     return;
                 }
-                let current_pos = filtered.iter().position(|&i| i == self.selected_skill);
+                let selection = self.tab_selection_mut();
+                let current_pos = filtered.iter().position(|i| i == selection);
                 if let Some(pos) = current_pos {
                     if pos < filtered.len() - 1 {
-                        self.selected_skill = filtered[pos + 1];
-                        self.skill_list_state.select(Some(self.selected_skill));
+                        *selection = filtered[pos + 1];
+                    }
+                } else {
+                    *selection = filtered[0];
+                }
+                self.skill_list_state.select(Some(self.selected_skill));
+            }
+            View::AllSkills => self.step_all_skills(1),
+            View::Available => {
+                let filtered = self.filtered_available_indices();
+                match filtered.iter().position(|&i| i == self.available_selection) {
+                    Some(pos) => {
+                        if let Some(&next) = filtered.get(pos + 1) {
+                            self.available_selection = next;
+                        }
+                    }
+                    None => {
+                        if let Some(&first) = filtered.first() {
+                            self.available_selection = first;
+                        }
                     }
-                } else if !filtered.is_empty() {
-                    self.selected_skill = filtered[0];
-                    self.skill_list_state.select(Some(self.selected_skill));
                 }
             }
-            View::LinkTargetSelect | View::InstallInput => {}
+            View::LinkTargetSelect
+            | View::InstallInput
+            | View::UpdatePreview
+            | View::SkillDetail
+            | View::Health
+            | View::MarketplaceInput
+            | View::GlobalSearch => {}
         }
     }
 }
+
+/// Unlink a skill from a target, keeping copies that were edited in place.
+fn unlink_unedited(skill: &Skill, target: &LinkTarget) -> Result<(), PluginError> {
+    if skill.link_status(target) == LinkStatus::Modified {
+        return Err(PluginError::CopyModified {
+            name: skill.name.clone(),
+            target: target.display_name().to_string(),
+        });
+    }
+    skill.unlink_from(target)
+}
@@ -0,0 +1,1064 @@
+//! Headless command-line interface.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::export::Export;
+use crate::manifest::{self, Manifest, SyncOptions};
+use crate::plugin::{
+    Component, Diagnostic, Health, LinkEntry, LinkMode, LinkStatus, LinkTarget, Lockfile,
+    Marketplace, MarketplaceEntry, Plugin, PluginError, PluginManager, Repair, SearchSource,
+    Setting, Settings, Severity, find_skill, lint_dir, parse_local_path,
+};
+
+/// Command-line arguments.
+#[derive(Debug, Parser)]
+#[command(
+    name = "skir",
+    version,
+    about = "Manage Claude Code plugins and skills"
+)]
+pub struct Cli {
+    /// Subcommand to run. Starts the TUI when omitted.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Offer the link targets of this project instead of the git work tree
+    /// containing the current directory
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+}
+
+/// Headless subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Install a plugin from a git URL
+    Install {
+        /// Git URL (HTTPS, SSH or owner/repo shorthand), optionally followed by
+        /// //subdir and ending in #branch, @tag or @sha; or a local directory
+        /// (./path, /path or file:///path)
+        url: String,
+        /// Install only this subdirectory of the repository
+        #[arg(long)]
+        subdir: Option<String>,
+        /// Check out the commit recorded in the manifest's lockfile
+        #[arg(long)]
+        locked: bool,
+        /// Use the lockfile of this manifest (skir.toml by default with --locked)
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+    /// List installed plugins and their skills
+    List {
+        /// Print machine-readable JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Update one plugin, or all of them
+    Update {
+        /// Plugin name (owner/repo or repo)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Update every installed plugin
+        #[arg(long)]
+        all: bool,
+        /// Print incoming commits and changed files without applying them
+        #[arg(long)]
+        dry_run: bool,
+        /// Maximum number of plugins to update at once (defaults to the
+        /// configured concurrency)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+    /// List plugins with new upstream commits
+    Outdated,
+    /// Restore the revision a plugin was at before its last update
+    Rollback {
+        /// Plugin name (owner/repo or repo)
+        name: String,
+        /// List previous revisions instead of rolling back
+        #[arg(long)]
+        list: bool,
+    },
+    /// Remove an installed plugin and unlink its skills
+    Remove {
+        /// Plugin name (owner/repo or repo)
+        name: String,
+    },
+    /// Link a skill, slash command or subagent into a target
+    Link {
+        /// Qualified skill, command or agent name (owner:repo:name)
+        qualified_name: String,
+        /// Link target, as named in the configuration (e.g. claude, codex or project-claude)
+        #[arg(long, default_value = "claude")]
+        target: String,
+        /// Symlink the skill, or copy it into the target (defaults to the target's mode)
+        #[arg(long, value_enum)]
+        mode: Option<ModeArg>,
+    },
+    /// Unlink a skill, slash command or subagent from a target
+    Unlink {
+        /// Qualified skill, command or agent name (owner:repo:name)
+        qualified_name: String,
+        /// Link target, as named in the configuration (e.g. claude, codex or project-claude)
+        #[arg(long, default_value = "claude")]
+        target: String,
+    },
+    /// Search skills of installed plugins and marketplaces by topic
+    Search {
+        /// Words to look for in skill names, descriptions and SKILL.md bodies
+        #[arg(required = true)]
+        terms: Vec<String>,
+        /// Show at most this many results
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Validate SKILL.md files in a directory or an installed plugin
+    Lint {
+        /// Directory to scan, or an installed plugin name (defaults to the current directory)
+        target: Option<String>,
+    },
+    /// Check link targets for broken, stray and unmanaged entries
+    Doctor {
+        /// Relink or remove broken links
+        #[arg(long)]
+        fix: bool,
+        /// Apply this repair to every problem it applies to
+        #[arg(long, value_enum, conflicts_with = "fix")]
+        repair: Option<RepairArg>,
+    },
+    /// Take over skills that were put into link targets by hand
+    Adopt {
+        /// Entries to adopt, by directory name (defaults to every unmanaged entry)
+        names: Vec<String>,
+        /// Register entries as local plugins even when they match an installed
+        /// skill or a git remote
+        #[arg(long)]
+        register: bool,
+        /// Print what would be done without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Reconcile installed plugins and links with a manifest
+    Sync {
+        /// Path to the manifest
+        #[arg(long, default_value = manifest::MANIFEST_FILE)]
+        manifest: PathBuf,
+        /// Remove installed plugins that are not listed in the manifest
+        #[arg(long)]
+        prune: bool,
+        /// Print the plan without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Check out the commits recorded in the manifest's lockfile
+        #[arg(long)]
+        locked: bool,
+    },
+    /// Add, browse and install from plugin marketplaces
+    Marketplace {
+        #[command(subcommand)]
+        command: MarketplaceCommand,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+/// Doctor repair as accepted on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RepairArg {
+    Relink,
+    Remove,
+    Adopt,
+    Replace,
+}
+
+impl From<RepairArg> for Repair {
+    fn from(arg: RepairArg) -> Self {
+        match arg {
+            RepairArg::Relink => Repair::Relink,
+            RepairArg::Remove => Repair::Remove,
+            RepairArg::Adopt => Repair::Adopt,
+            RepairArg::Replace => Repair::Replace,
+        }
+    }
+}
+
+/// `skir marketplace` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum MarketplaceCommand {
+    /// Add a marketplace from a git URL (optionally ending in #branch) or a local directory
+    Add { source: String },
+    /// List marketplaces and the plugins they offer
+    List {
+        /// Only list this marketplace
+        name: Option<String>,
+    },
+    /// Fetch the latest catalog of one marketplace, or all of them
+    Update { name: Option<String> },
+    /// Remove a marketplace; plugins installed from it stay installed
+    Remove { name: String },
+    /// Install a plugin listed in a marketplace
+    Install {
+        /// Plugin name, or plugin@marketplace when several marketplaces list it
+        plugin: String,
+    },
+}
+
+/// `skir config` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the effective configuration and where each value came from
+    Show,
+}
+
+/// Link mode as accepted on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ModeArg {
+    Symlink,
+    Copy,
+}
+
+impl From<ModeArg> for LinkMode {
+    fn from(arg: ModeArg) -> Self {
+        match arg {
+            ModeArg::Symlink => LinkMode::Symlink,
+            ModeArg::Copy => LinkMode::Copy,
+        }
+    }
+}
+
+/// Run a subcommand and map its outcome to a process exit code.
+pub fn run(command: Command) -> ExitCode {
+    let result = PluginManager::new()
+        .map(manifest::with_project_lockfile)
+        .and_then(|manager| match command {
+            Command::Install {
+                url,
+                subdir,
+                locked,
+                manifest,
+            } => {
+                // --locked reads the lockfile `skir sync` writes, next to the manifest
+                let manifest =
+                    manifest.or_else(|| locked.then(|| PathBuf::from(manifest::MANIFEST_FILE)));
+                let manager = match manifest {
+                    Some(path) => manifest::with_manifest_lockfile(manager, &path)?,
+                    None => manager,
+                };
+                install(&manager, &url, subdir.as_deref(), locked)
+            }
+            Command::List { json } => list(&manager, json),
+            Command::Update {
+                name,
+                all,
+                dry_run,
+                jobs,
+            } => {
+                let manager = match jobs {
+                    Some(jobs) => manager.with_concurrency(jobs),
+                    None => manager,
+                };
+                update(manager, name.as_deref(), all, dry_run)
+            }
+            Command::Outdated => outdated(&manager),
+            Command::Rollback { name, list } => rollback(&manager, &name, list),
+            Command::Remove { name } => remove(&manager, &name),
+            Command::Link {
+                qualified_name,
+                target,
+                mode,
+            } => find_target(&target)
+                .and_then(|target| link(&manager, &qualified_name, target, mode.map(Into::into))),
+            Command::Unlink {
+                qualified_name,
+                target,
+            } => find_target(&target).and_then(|target| unlink(&manager, &qualified_name, target)),
+            Command::Search { terms, limit } => search(&manager, &terms.join(" "), limit),
+            Command::Lint { target } => lint(&manager, target.as_deref()),
+            Command::Doctor { fix, repair } => doctor(&manager, fix, repair.map(Into::into)),
+            Command::Adopt {
+                names,
+                register,
+                dry_run,
+            } => adopt(&manager, &names, register, dry_run),
+            Command::Sync {
+                manifest,
+                prune,
+                dry_run,
+                locked,
+            } => sync(manager, &manifest, prune, dry_run, locked),
+            Command::Marketplace { command } => marketplace(&manager, command),
+            Command::Config {
+                command: ConfigCommand::Show,
+            } => {
+                config_show(Settings::get()?);
+                Ok(())
+            }
+        });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Format a plugin as `owner/repo`.
+fn plugin_label(plugin: &Plugin) -> String {
+    format!("{}/{}", plugin.owner, plugin.name())
+}
+
+/// Find a linkable bundle component (a command or agent) by qualified name.
+fn find_component<'a>(plugins: &'a [Arc<Plugin>], qualified_name: &str) -> Option<&'a Component> {
+    plugins
+        .iter()
+        .flat_map(|p| p.components())
+        .find(|c| c.kind.is_linkable() && c.qualified_name() == qualified_name)
+}
+
+fn install(
+    manager: &PluginManager,
+    url: &str,
+    subdir: Option<&str>,
+    locked: bool,
+) -> Result<(), PluginError> {
+    if let Some(dir) = parse_local_path(url) {
+        // Local directories have no commits to lock
+        if locked {
+            return Err(PluginError::NotLocked {
+                name: url.to_string(),
+            });
+        }
+        let dir = match subdir {
+            Some(subdir) => dir.join(subdir),
+            None => dir,
+        };
+        let plugin = manager.install_local(&dir)?;
+        println!(
+            "Registered {} ({} skills)",
+            plugin_label(&plugin),
+            plugin.skills().len()
+        );
+        return Ok(());
+    }
+
+    let mut source = manager.parse_source(url)?;
+    if let Some(subdir) = subdir {
+        source.set_subdir(subdir)?;
+    }
+
+    let plugin = if locked {
+        manager.install_locked(source)?
+    } else {
+        manager.install_source(source)?
+    };
+    println!(
+        "Installed {} ({} skills)",
+        plugin_label(&plugin),
+        plugin.skills().len()
+    );
+    Ok(())
+}
+
+fn list(manager: &PluginManager, json: bool) -> Result<(), PluginError> {
+    let plugins = manager.list_installed()?;
+    if json {
+        println!(
+            "{}",
+            Export::from_plugins(&plugins, LinkTarget::all()).to_json()
+        );
+        return Ok(());
+    }
+
+    if plugins.is_empty() {
+        println!("No plugins installed.");
+        return Ok(());
+    }
+
+    for plugin in &plugins {
+        match (&plugin.reference, plugin.local_dir()) {
+            (Some(reference), _) => println!("{} {}", plugin_label(plugin), reference),
+            (None, Some(dir)) => println!("{} (local: {})", plugin_label(plugin), dir.display()),
+            (None, None) => println!("{}", plugin_label(plugin)),
+        }
+        for error in &plugin.bundle_errors {
+            eprintln!("warning: {}: {}", plugin.path.display(), error);
+        }
+        for skill in plugin.skills() {
+            if let Some(error) = &skill.frontmatter_error {
+                eprintln!("warning: {}: {}", skill.path.display(), error);
+            }
+            let linked: Vec<String> = LinkTarget::all()
+                .iter()
+                .filter_map(|t| {
+                    let note = match skill.link_status(t) {
+                        LinkStatus::NotLinked => return None,
+                        LinkStatus::Symlinked => "",
+                        LinkStatus::Copied => " (copy)",
+                        LinkStatus::Outdated => " (copy, outdated)",
+                        LinkStatus::Modified => " (copy, edited locally)",
+                    };
+                    Some(format!("{}{}", t.display_name(), note))
+                })
+                .collect();
+            if linked.is_empty() {
+                println!("  {}", skill.qualified_name());
+            } else {
+                println!(
+                    "  {}  [linked: {}]",
+                    skill.qualified_name(),
+                    linked.join(", ")
+                );
+            }
+        }
+        for component in plugin.components() {
+            let linked: Vec<&str> = LinkTarget::all()
+                .iter()
+                .filter(|t| component.is_linked_to(t))
+                .map(|t| t.display_name())
+                .collect();
+            let label = format!("{} ({})", component.qualified_name(), component.kind.id());
+            if linked.is_empty() {
+                println!("  {}", label);
+            } else {
+                println!("  {}  [linked: {}]", label, linked.join(", "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn update(
+    manager: PluginManager,
+    name: Option<&str>,
+    all: bool,
+    dry_run: bool,
+) -> Result<(), PluginError> {
+    let plugins = if all {
+        manager.list_installed()?
+    } else {
+        // clap guarantees a name when --all is absent
+        vec![manager.find_installed(name.unwrap_or_default())?]
+    };
+
+    if dry_run {
+        return preview(&manager, &plugins);
+    }
+
+    let total = plugins.len();
+    let mut failures = Vec::new();
+    for (done, (plugin, result)) in manager.update_all(plugins).iter().enumerate() {
+        let progress = if total > 1 {
+            format!("[{}/{}] ", done + 1, total)
+        } else {
+            String::new()
+        };
+        match result {
+            Ok(updated) => match &updated.reference {
+                _ if updated.is_local() => {
+                    println!("{}Rescanned {} (local)", progress, plugin_label(&plugin))
+                }
+                Some(reference) if updated.is_fixed() => {
+                    println!(
+                        "{}Rescanned {} (pinned to {})",
+                        progress,
+                        plugin_label(&plugin),
+                        reference
+                    )
+                }
+                _ => println!("{}Updated {}", progress, plugin_label(&plugin)),
+            },
+            Err(e) if total > 1 => {
+                // Details follow in the summary
+                eprintln!("{}Update failed for {}", progress, plugin_label(&plugin));
+                failures.push((plugin, e));
+            }
+            Err(e) => failures.push((plugin, e)),
+        }
+    }
+
+    if total > 1 {
+        println!("Updated {}/{} plugins", total - failures.len(), total);
+        if !failures.is_empty() {
+            eprintln!("Failed:");
+            for (plugin, e) in &failures {
+                eprintln!("  {}: {}", plugin_label(plugin), e);
+            }
+        }
+    }
+
+    match failures.pop() {
+        Some((_, e)) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Print what updating each plugin would change.
+fn preview(manager: &PluginManager, plugins: &[Arc<Plugin>]) -> Result<(), PluginError> {
+    for plugin in plugins.iter().filter(|p| !p.is_rescan_only()) {
+        let pending = manager.fetch_update(plugin)?;
+        if pending.is_up_to_date() {
+            println!("{} is up to date", plugin_label(plugin));
+            continue;
+        }
+
+        println!(
+            "{} ({} commits):",
+            plugin_label(plugin),
+            pending.commits.len()
+        );
+        for commit in &pending.commits {
+            println!(
+                "  {} {}",
+                &commit.sha[..commit.sha.len().min(7)],
+                commit.summary
+            );
+        }
+        for change in &pending.changes {
+            let skill = change.skill.as_deref().unwrap_or("(other files)");
+            for file in &change.files {
+                println!("  {} {}  [{}]", file.status.marker(), file.path, skill);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn outdated(manager: &PluginManager) -> Result<(), PluginError> {
+    let mut outdated = 0;
+    let mut last_error = None;
+    for plugin in &manager.list_installed()? {
+        match manager.commits_behind(plugin) {
+            Ok(Some(count)) if count > 0 => {
+                println!("{}  {} new commits", plugin_label(plugin), count);
+                outdated += 1;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Update check failed for {}: {}", plugin_label(plugin), e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    if outdated == 0 && last_error.is_none() {
+        println!("All plugins are up to date.");
+    }
+
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn search(manager: &PluginManager, query: &str, limit: usize) -> Result<(), PluginError> {
+    let index = manager.search_index(&list_marketplaces(manager))?;
+    let results = index.search(query);
+    if results.is_empty() {
+        println!("No skills match {:?}.", query);
+        return Ok(());
+    }
+
+    for result in results.iter().take(limit) {
+        let doc = index.doc(result.doc);
+        match &doc.source {
+            SearchSource::Installed { .. } => println!("{}", doc.label()),
+            SearchSource::Available { .. } => println!("{}  [not installed]", doc.label()),
+        }
+        if let Some(description) = doc.description.as_deref().and_then(|d| d.lines().next()) {
+            println!("    {}", description);
+        }
+        if let Some(snippet) = &result.snippet {
+            println!("    > {}", snippet);
+        }
+    }
+    if results.len() > limit {
+        println!("... and {} more", results.len() - limit);
+    }
+    Ok(())
+}
+
+fn rollback(manager: &PluginManager, name: &str, list: bool) -> Result<(), PluginError> {
+    let plugin = manager.find_installed(name)?;
+    if list {
+        let history = plugin.history();
+        if history.is_empty() {
+            println!("No previous revisions of {}", plugin_label(&plugin));
+        }
+        for commit in history {
+            println!("{}", commit);
+        }
+        return Ok(());
+    }
+
+    let rolled_back = manager.rollback(&plugin)?;
+    let commit = rolled_back.head_commit().unwrap_or_default();
+    println!(
+        "Rolled back {} to {}",
+        plugin_label(&plugin),
+        &commit[..commit.len().min(12)]
+    );
+    Ok(())
+}
+
+fn remove(manager: &PluginManager, name: &str) -> Result<(), PluginError> {
+    let plugin = manager.find_installed(name)?;
+    manager.remove(&plugin)?;
+    println!("Removed {}", plugin_label(&plugin));
+    Ok(())
+}
+
+/// Look up a configured link target by name.
+fn find_target(name: &str) -> Result<&'static LinkTarget, PluginError> {
+    LinkTarget::from_id(name).ok_or_else(|| PluginError::UnknownTarget {
+        name: name.to_string(),
+        available: LinkTarget::all()
+            .iter()
+            .map(|t| t.id())
+            .collect::<Vec<_>>()
+            .join(", "),
+    })
+}
+
+fn link(
+    manager: &PluginManager,
+    qualified_name: &str,
+    target: &LinkTarget,
+    mode: Option<LinkMode>,
+) -> Result<(), PluginError> {
+    let plugins = manager.list_installed()?;
+    if let Some(component) = find_component(&plugins, qualified_name) {
+        // Commands and agents are single files, always symlinked
+        if mode == Some(LinkMode::Copy) {
+            return Err(PluginError::LinkFailed {
+                name: qualified_name.to_string(),
+                reason: format!("{}s cannot be copied", component.kind.id()),
+            });
+        }
+        component.link_to(target)?;
+        println!("Linked {} to {}", qualified_name, target.display_name());
+        return Ok(());
+    }
+
+    let skill = find_skill(&plugins, qualified_name)?;
+    let mode = mode.unwrap_or(target.mode);
+    skill.link_to_with(target, mode)?;
+    match mode {
+        LinkMode::Symlink => println!("Linked {} to {}", qualified_name, target.display_name()),
+        LinkMode::Copy => println!("Copied {} to {}", qualified_name, target.display_name()),
+    }
+    Ok(())
+}
+
+fn unlink(
+    manager: &PluginManager,
+    qualified_name: &str,
+    target: &LinkTarget,
+) -> Result<(), PluginError> {
+    let plugins = manager.list_installed()?;
+    match find_component(&plugins, qualified_name) {
+        Some(component) => component.unlink_from(target)?,
+        None => find_skill(&plugins, qualified_name)?.unlink_from(target)?,
+    }
+    println!("Unlinked {} from {}", qualified_name, target.display_name());
+    Ok(())
+}
+
+fn lint(manager: &PluginManager, target: Option<&str>) -> Result<(), PluginError> {
+    let target = target.unwrap_or(".");
+    let path = parse_local_path(target).unwrap_or_else(|| PathBuf::from(target));
+
+    // Prefer a directory of that name over an installed plugin
+    let diagnostics: Vec<Diagnostic> = if path.is_dir() {
+        lint_dir(&path)?
+    } else {
+        let plugin = manager.find_installed(target)?;
+        plugin
+            .skills()
+            .iter()
+            .flat_map(|s| s.diagnostics().to_vec())
+            .collect()
+    };
+
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    println!("{} errors, {} warnings", errors, warnings);
+
+    if errors > 0 {
+        return Err(PluginError::LintFailed { errors });
+    }
+    Ok(())
+}
+
+fn doctor(manager: &PluginManager, fix: bool, repair: Option<Repair>) -> Result<(), PluginError> {
+    let entries = manager.doctor()?;
+    let mut problems = 0;
+
+    for target in LinkTarget::all().iter().filter(|t| t.dir.is_dir()) {
+        let entries: Vec<&LinkEntry> = entries.iter().filter(|e| e.target == *target).collect();
+        let healthy = entries
+            .iter()
+            .filter(|e| e.health == Health::Healthy)
+            .count();
+        println!(
+            "{} ({}): {} healthy",
+            target.display_name(),
+            target.dir.display(),
+            healthy
+        );
+
+        for entry in entries.iter().filter(|e| e.health != Health::Healthy) {
+            let points_to = match &entry.points_to {
+                Some(path) => format!(" -> {}", path.display()),
+                None => String::new(),
+            };
+            println!("  {:<13} {}{}", entry.health.id(), entry.name(), points_to);
+            if let Some(origin) = &entry.origin {
+                println!("    {}", origin);
+            } else if let (Health::Unmanaged, Some(skill)) = (entry.health, &entry.skill) {
+                println!("    in the way of {}", skill);
+            } else if let (Health::NotInstalled, Some(skill)) = (entry.health, &entry.skill) {
+                println!("    copy of {}; install its plugin to update it", skill);
+                continue;
+            }
+
+            let repairs = entry.repairs();
+            let chosen = match repair {
+                Some(repair) => repairs.contains(&repair).then_some(repair),
+                None if fix => entry.fix(),
+                None => None,
+            };
+            match chosen {
+                Some(repair) => match manager.repair(entry, repair) {
+                    Ok(()) => println!("    fixed: {}", repair.id()),
+                    Err(e) => {
+                        println!("    {} failed: {}", repair.id(), e);
+                        problems += 1;
+                    }
+                },
+                None => {
+                    let repairs: Vec<&str> = repairs.iter().map(|r| r.id()).collect();
+                    if repairs.is_empty() {
+                        println!("    no automatic repair; move or delete it by hand");
+                    } else {
+                        println!("    repair: {}", repairs.join(", "));
+                    }
+                    if entry.is_problem() {
+                        problems += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    if problems > 0 {
+        if !fix && repair.is_none() {
+            println!(
+                "Run `skir doctor --fix` to relink or remove broken links, or --repair <repair>."
+            );
+        }
+        return Err(PluginError::UnhealthyLinks { problems });
+    }
+    Ok(())
+}
+
+fn adopt(
+    manager: &PluginManager,
+    names: &[String],
+    register: bool,
+    dry_run: bool,
+) -> Result<(), PluginError> {
+    let entries: Vec<LinkEntry> = manager
+        .doctor()?
+        .into_iter()
+        .filter(|e| e.health == Health::Unmanaged)
+        .filter(|e| names.is_empty() || names.contains(&e.name()))
+        .collect();
+    if let Some(name) = names
+        .iter()
+        .find(|n| !entries.iter().any(|e| e.name() == **n))
+    {
+        return Err(PluginError::SkillNotFound { name: name.clone() });
+    }
+    if entries.is_empty() {
+        println!("No unmanaged skills in any link target.");
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    for entry in &entries {
+        let label = format!("{}: {}", entry.target.display_name(), entry.name());
+        let repair = if register {
+            Repair::Adopt
+        } else {
+            entry.repairs().first().copied().unwrap_or(Repair::Adopt)
+        };
+        let plan = match (repair, &entry.origin) {
+            (Repair::Replace, Some(origin)) => format!("replace with a link ({})", origin),
+            _ => "register as a local plugin".to_string(),
+        };
+
+        if dry_run {
+            println!("{}: would {}", label, plan);
+            continue;
+        }
+        match manager.repair(entry, repair) {
+            Ok(()) => println!("{}: {}", label, plan),
+            Err(e) => {
+                eprintln!("{}: failed: {}", label, e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(PluginError::AdoptFailed { failures });
+    }
+    Ok(())
+}
+
+fn marketplace(manager: &PluginManager, command: MarketplaceCommand) -> Result<(), PluginError> {
+    let select = |name: Option<String>| -> Result<Vec<Marketplace>, PluginError> {
+        match name {
+            Some(name) => Ok(vec![manager.find_marketplace(&name)?]),
+            None => Ok(list_marketplaces(manager)),
+        }
+    };
+
+    match command {
+        MarketplaceCommand::Add { source } => {
+            let marketplace = manager.add_marketplace(&source)?;
+            println!(
+                "Added {} ({} plugins)",
+                marketplace.name,
+                marketplace.plugins.len()
+            );
+        }
+        MarketplaceCommand::List { name } => {
+            let marketplaces = select(name)?;
+            if marketplaces.is_empty() {
+                println!("No marketplaces added.");
+            }
+            for marketplace in &marketplaces {
+                println!("{} ({})", marketplace.name, marketplace.source);
+                for entry in &marketplace.plugins {
+                    let version = entry
+                        .version
+                        .as_ref()
+                        .map(|v| format!(" v{}", v))
+                        .unwrap_or_default();
+                    let installed = if manager.is_entry_installed(marketplace, entry) {
+                        "  [installed]"
+                    } else {
+                        ""
+                    };
+                    println!("  {}{}{}", entry.name, version, installed);
+                    if let Some(description) =
+                        entry.description.as_deref().and_then(|d| d.lines().next())
+                    {
+                        println!("      {}", description);
+                    }
+                }
+            }
+        }
+        MarketplaceCommand::Update { name } => {
+            for marketplace in select(name)? {
+                let updated = manager.update_marketplace(&marketplace)?;
+                println!(
+                    "Updated {} ({} plugins)",
+                    updated.name,
+                    updated.plugins.len()
+                );
+            }
+        }
+        MarketplaceCommand::Remove { name } => {
+            let marketplace = manager.find_marketplace(&name)?;
+            manager.remove_marketplace(&marketplace)?;
+            println!("Removed {}", marketplace.name);
+        }
+        MarketplaceCommand::Install { plugin } => {
+            let (marketplace, entry) = find_entry(manager, &plugin)?;
+            let installed = manager.install_entry(&marketplace, &entry)?;
+            println!(
+                "Installed {} from {} ({} skills)",
+                plugin_label(&installed),
+                marketplace.name,
+                installed.skills().len()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// List the added marketplaces, warning about those that cannot be read.
+fn list_marketplaces(manager: &PluginManager) -> Vec<Marketplace> {
+    let (marketplaces, errors) = manager.list_marketplaces();
+    for error in errors {
+        eprintln!("warning: {}", error);
+    }
+    marketplaces
+}
+
+/// Find a marketplace entry by `name` or `name@marketplace`.
+fn find_entry(
+    manager: &PluginManager,
+    spec: &str,
+) -> Result<(Marketplace, MarketplaceEntry), PluginError> {
+    let (name, marketplaces) = match spec.split_once('@') {
+        Some((name, marketplace)) => (name, vec![manager.find_marketplace(marketplace)?]),
+        None => (spec, list_marketplaces(manager)),
+    };
+
+    let mut matches: Vec<(Marketplace, MarketplaceEntry)> = marketplaces
+        .into_iter()
+        .filter_map(|m| {
+            let entry = m.entry(name)?.clone();
+            Some((m, entry))
+        })
+        .collect();
+    match matches.len() {
+        0 => Err(PluginError::EntryNotFound {
+            name: spec.to_string(),
+        }),
+        1 => Ok(matches.remove(0)),
+        _ => Err(PluginError::AmbiguousName {
+            name: spec.to_string(),
+            candidates: matches
+                .iter()
+                .map(|(m, e)| format!("{}@{}", e.name, m.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }),
+    }
+}
+
+fn sync(
+    manager: PluginManager,
+    path: &Path,
+    prune: bool,
+    dry_run: bool,
+    locked: bool,
+) -> Result<(), PluginError> {
+    let manifest = Manifest::load(path, LinkTarget::all())?;
+    // The lockfile lives next to the manifest so it can be checked in with it
+    let keys = manifest::lock_keys(&manifest, manager.default_host());
+    let manager = manager.with_project_lockfile(manifest::lockfile_path(path), keys);
+    let lockfile = if locked {
+        Some(Lockfile::load(&manifest::lockfile_path(path))?)
+    } else {
+        None
+    };
+    let options = SyncOptions {
+        prune,
+        lockfile,
+        default_host: Some(manager.default_host().to_string()),
+        targets: LinkTarget::all().to_vec(),
+    };
+    let plan = manifest::plan(&manifest, &manager.list_installed()?, &options)?;
+
+    for warning in &plan.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    if plan.is_empty() {
+        println!("Already in sync.");
+        if !dry_run && !locked {
+            manifest::lock(&manifest, &manager)?;
+        }
+        return Ok(());
+    }
+
+    println!("Plan:");
+    for action in &plan.actions {
+        println!("  {}", action);
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    let failures = manifest::apply(&plan, &manager);
+    if !locked {
+        manifest::lock(&manifest, &manager)?;
+    }
+    let applied = plan.actions.len() - failures.len();
+    println!("Applied {}/{} actions", applied, plan.actions.len());
+
+    let mut last_error = None;
+    for (action, e) in failures {
+        eprintln!("Failed to {}: {}", action, e);
+        last_error = Some(e);
+    }
+
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Print the effective configuration as TOML, with the source of each value
+/// in a trailing comment.
+fn config_show(settings: &Settings) {
+    fn value<T: Into<toml::Value>>(value: T) -> String {
+        value.into().to_string()
+    }
+    fn row<T: Clone + Into<toml::Value>>(key: &str, setting: &Setting<T>) {
+        let line = format!("{} = {}", key, value(setting.value.clone()));
+        println!("{:<48} # {}", line, setting.source);
+    }
+
+    match &settings.config_path {
+        Some(path) if path.exists() => println!("# config file: {}", path.display()),
+        Some(path) => println!("# config file: {} (not found)", path.display()),
+        None => println!("# config file: none"),
+    }
+    println!();
+
+    let cache_dir = Setting {
+        value: settings.cache_dir.value.display().to_string(),
+        source: settings.cache_dir.source.clone(),
+    };
+    row("cache-dir", &cache_dir);
+    let data_dir = Setting {
+        value: settings.data_dir.value.display().to_string(),
+        source: settings.data_dir.source.clone(),
+    };
+    row("data-dir", &data_dir);
+    row("default-host", &settings.default_host);
+    let concurrency = Setting {
+        value: i64::try_from(settings.concurrency.value).unwrap_or(i64::MAX),
+        source: settings.concurrency.source.clone(),
+    };
+    row("concurrency", &concurrency);
+
+    println!();
+    println!("[ui]");
+    row("show-preview", &settings.show_preview);
+    row("check-updates", &settings.check_updates);
+
+    for (target, source) in settings.targets.iter().zip(&settings.target_sources) {
+        println!();
+        println!("{:<48} # {}", "[[targets]]", source);
+        println!("name = {}", value(target.name.as_str()));
+        println!("display-name = {}", value(target.display_name.as_str()));
+        println!("dir = {}", value(target.dir.display().to_string()));
+        println!("mode = {}", value(target.mode.id()));
+        println!("naming = {}", value(target.naming.id()));
+        println!("relative = {}", value(target.relative));
+    }
+}
@@ -0,0 +1,251 @@
+//! Machine-readable export of installed plugins and skills.
+//!
+//! The JSON layout is versioned by [`SCHEMA_VERSION`]. Fields may be added
+//! within a version; renaming or removing a field bumps the version.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::plugin::{Component, LinkTarget, Plugin, Skill};
+
+/// Current version of the export schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Top-level export document.
+#[derive(Debug, Serialize)]
+pub struct Export {
+    /// Version of this schema.
+    pub schema_version: u32,
+    /// All installed plugins.
+    pub plugins: Vec<PluginExport>,
+}
+
+/// An installed plugin.
+#[derive(Debug, Serialize)]
+pub struct PluginExport {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    /// Local path of the plugin checkout.
+    pub path: PathBuf,
+    /// Commit SHA currently checked out, if known.
+    pub commit: Option<String>,
+    /// The ref the plugin is pinned to, if any.
+    #[serde(rename = "ref")]
+    pub reference: Option<RefExport>,
+    /// Subdirectory the plugin is scoped to, if any.
+    pub subdir: Option<String>,
+    /// Directory a local plugin refers to; `null` for git plugins.
+    pub local_dir: Option<PathBuf>,
+    /// Version from `.claude-plugin/plugin.json`, if the plugin has one.
+    pub version: Option<String>,
+    pub skills: Vec<SkillExport>,
+    /// Commands, agents, hooks and MCP servers of a plugin bundle.
+    pub components: Vec<ComponentExport>,
+}
+
+/// A pinned git ref.
+#[derive(Debug, Serialize)]
+pub struct RefExport {
+    /// One of `branch`, `tag` or `commit`.
+    pub kind: &'static str,
+    pub name: String,
+}
+
+/// A skill within a plugin.
+#[derive(Debug, Serialize)]
+pub struct SkillExport {
+    pub name: String,
+    pub qualified_name: String,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    pub allowed_tools: Vec<String>,
+    /// Frontmatter keys skir does not know about.
+    pub extra: BTreeMap<String, serde_json::Value>,
+    /// Why the frontmatter could not be parsed, if it could not.
+    pub frontmatter_error: Option<String>,
+    /// Path to the skill's SKILL.md file.
+    pub path: PathBuf,
+    /// Link state keyed by target id (e.g. `claude`, `codex`).
+    pub links: BTreeMap<String, LinkExport>,
+}
+
+/// A bundle component within a plugin.
+#[derive(Debug, Serialize)]
+pub struct ComponentExport {
+    /// One of `command`, `agent`, `hook` or `mcp-server`.
+    pub kind: &'static str,
+    pub name: String,
+    pub qualified_name: String,
+    pub description: Option<String>,
+    /// The file defining the component.
+    pub path: PathBuf,
+    /// Ids of the targets the component is linked to.
+    pub linked: Vec<String>,
+}
+
+/// Link state of a skill for one target.
+#[derive(Debug, Serialize)]
+pub struct LinkExport {
+    pub linked: bool,
+    /// `symlink` or `copy`; `null` when not linked.
+    pub mode: Option<&'static str>,
+    /// One of `not-linked`, `symlinked`, `copied`, `outdated` or `modified`.
+    pub status: &'static str,
+    /// Where the link lives (or would live) for this target.
+    pub link_path: PathBuf,
+}
+
+impl Export {
+    /// Build an export document from installed plugins, with their links in `targets`.
+    pub fn from_plugins(plugins: &[Arc<Plugin>], targets: &[LinkTarget]) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            plugins: plugins
+                .iter()
+                .map(|p| PluginExport::from_plugin(p, targets))
+                .collect(),
+        }
+    }
+
+    /// Serialize to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        // Serialization of these plain data types cannot fail
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl PluginExport {
+    fn from_plugin(plugin: &Plugin, targets: &[LinkTarget]) -> Self {
+        Self {
+            host: plugin.host.clone(),
+            owner: plugin.owner.clone(),
+            repo: plugin.repo.clone(),
+            path: plugin.path.clone(),
+            commit: plugin.head_commit(),
+            reference: plugin.reference.as_ref().map(|r| RefExport {
+                kind: r.kind(),
+                name: r.name().to_string(),
+            }),
+            subdir: plugin.subdir.clone(),
+            local_dir: plugin.local_dir(),
+            version: plugin.manifest.as_ref().and_then(|m| m.version.clone()),
+            skills: plugin
+                .skills()
+                .iter()
+                .map(|s| SkillExport::from_skill(s, targets))
+                .collect(),
+            components: plugin
+                .components()
+                .iter()
+                .map(|c| ComponentExport::from_component(c, targets))
+                .collect(),
+        }
+    }
+}
+
+impl SkillExport {
+    fn from_skill(skill: &Skill, targets: &[LinkTarget]) -> Self {
+        let links = targets
+            .iter()
+            .map(|target| {
+                let status = skill.link_status(target);
+                let link = LinkExport {
+                    linked: status.is_linked(),
+                    mode: skill.link_mode(target).map(|m| m.id()),
+                    status: status.id(),
+                    link_path: skill.link_path_for(target),
+                };
+                (target.id().to_string(), link)
+            })
+            .collect();
+
+        Self {
+            name: skill.name.clone(),
+            qualified_name: skill.qualified_name(),
+            description: skill.description().map(String::from),
+            version: skill.metadata.version.clone(),
+            license: skill.metadata.license.clone(),
+            allowed_tools: skill.metadata.allowed_tools.clone(),
+            extra: skill.metadata.extra.clone(),
+            frontmatter_error: skill.frontmatter_error.as_ref().map(ToString::to_string),
+            path: skill.path.clone(),
+            links,
+        }
+    }
+}
+
+impl ComponentExport {
+    fn from_component(component: &Component, targets: &[LinkTarget]) -> Self {
+        Self {
+            kind: component.kind.id(),
+            name: component.name.clone(),
+            qualified_name: component.qualified_name(),
+            description: component.description.clone(),
+            path: component.path.clone(),
+            linked: targets
+                .iter()
+                .filter(|t| component.is_linked_to(t))
+                .map(|t| t.id().to_string())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::testing::targets;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_export_schema() {
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join("skills").join("foo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\ndescription: Foo skill\n---\n",
+        )
+        .unwrap();
+
+        let plugin = Plugin::build(
+            "github.com".to_string(),
+            "anthropics".to_string(),
+            "skills".to_string(),
+            dir.path().to_path_buf(),
+        )
+        .unwrap();
+
+        let targets = targets(&dir.path().join("targets"));
+        plugin.skills()[0].link_to(&targets[0]).unwrap();
+
+        let export = Export::from_plugins(&[Arc::new(plugin)], &targets);
+        let value: serde_json::Value = serde_json::from_str(&export.to_json()).unwrap();
+
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        let plugin = &value["plugins"][0];
+        assert_eq!(plugin["host"], "github.com");
+        assert_eq!(plugin["owner"], "anthropics");
+        assert_eq!(plugin["repo"], "skills");
+        assert!(plugin["commit"].is_null());
+        assert!(plugin["ref"].is_null());
+        assert!(plugin["local_dir"].is_null());
+
+        let skill = &plugin["skills"][0];
+        assert_eq!(skill["name"], "foo");
+        assert_eq!(skill["qualified_name"], "anthropics:skills:foo");
+        assert_eq!(skill["description"], "Foo skill");
+        assert!(skill["frontmatter_error"].is_null());
+        assert_eq!(skill["links"]["claude"]["linked"], true);
+        assert_eq!(skill["links"]["claude"]["mode"], "symlink");
+        assert_eq!(skill["links"]["codex"]["linked"], false);
+        assert_eq!(skill["links"]["codex"]["status"], "not-linked");
+        assert_eq!(plugin["components"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,158 @@
+//! Filtering of the all-skills view by link state.
+
+use skir::{LinkTarget, Skill};
+
+/// Which skills the all-skills view shows, by their link state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillFilter {
+    All,
+    Linked,
+    Unlinked,
+    Broken,
+}
+
+impl SkillFilter {
+    /// Get the filter after this one, wrapping around.
+    pub fn next(self) -> SkillFilter {
+        match self {
+            SkillFilter::All => SkillFilter::Linked,
+            SkillFilter::Linked => SkillFilter::Unlinked,
+            SkillFilter::Unlinked => SkillFilter::Broken,
+            SkillFilter::Broken => SkillFilter::All,
+        }
+    }
+
+    /// Get the name shown in the view's header.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkillFilter::All => "all",
+            SkillFilter::Linked => "linked",
+            SkillFilter::Unlinked => "unlinked",
+            SkillFilter::Broken => "broken",
+        }
+    }
+
+    /// Check if a skill passes this filter in the given targets.
+    ///
+    /// Linked and broken skills have such a link in any of the targets;
+    /// unlinked ones are linked in none of them.
+    pub fn matches(&self, skill: &Skill, targets: &[&LinkTarget]) -> bool {
+        match self {
+            SkillFilter::All => true,
+            SkillFilter::Linked => targets.iter().any(|t| skill.is_linked_to(t)),
+            SkillFilter::Unlinked => !targets.iter().any(|t| skill.is_linked_to(t)),
+            SkillFilter::Broken => targets.iter().any(|t| skill.is_link_broken(t)),
+        }
+    }
+}
+
+/// Move a selection by `delta` rows of a filtered list, given as the sorted
+/// indices that pass the filter.
+///
+/// A selection that no longer passes the filter moves to the nearest row
+/// after it, or the last row if there is none; stepping from there counts
+/// that row as the first step. Returns `None` if nothing passes.
+pub fn step(filtered: &[usize], selection: usize, delta: isize) -> Option<usize> {
+    let last = filtered.len().checked_sub(1)?;
+    let pos = match filtered.binary_search(&selection) {
+        Ok(pos) => pos.saturating_add_signed(delta),
+        Err(pos) if delta < 0 => pos.saturating_add_signed(delta),
+        Err(pos) => pos.saturating_add_signed((delta - 1).max(0)),
+    };
+    Some(filtered[pos.min(last)])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use skir::Plugin;
+    use skir::plugin::{LinkMode, Naming};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn target(name: &str, dir: &Path) -> LinkTarget {
+        LinkTarget {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            dir: dir.join(name),
+            mode: LinkMode::Symlink,
+            naming: Naming::Qualified,
+            relative: false,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let cache = tempdir().unwrap();
+        let dir = tempdir().unwrap();
+        for name in ["docx", "pdf", "zip"] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+            fs::write(dir.path().join(name).join("SKILL.md"), "").unwrap();
+        }
+        let path = cache.path().join("local/local/skills");
+        let plugin = Plugin::install_local(dir.path().to_path_buf(), path).unwrap();
+        let skill = |name: &str| plugin.skills().iter().find(|s| s.name == name).unwrap();
+        let (docx, pdf, zip) = (skill("docx"), skill("pdf"), skill("zip"));
+
+        let claude = target("claude", cache.path());
+        let codex = target("codex", cache.path());
+        // docx is linked to Claude only; pdf's link in Codex is broken
+        docx.link_to(&claude).unwrap();
+        pdf.link_to(&codex).unwrap();
+        fs::remove_dir_all(dir.path().join("pdf")).unwrap();
+
+        let filtered = |filter: SkillFilter, targets: &[&LinkTarget]| -> Vec<&str> {
+            [docx, pdf, zip]
+                .into_iter()
+                .filter(|s| filter.matches(s, targets))
+                .map(|s| s.name.as_str())
+                .collect()
+        };
+        let both = [&claude, &codex];
+        assert_eq!(filtered(SkillFilter::All, &both), ["docx", "pdf", "zip"]);
+        assert_eq!(filtered(SkillFilter::Linked, &both), ["docx"]);
+        assert_eq!(filtered(SkillFilter::Unlinked, &both), ["pdf", "zip"]);
+        assert_eq!(filtered(SkillFilter::Broken, &both), ["pdf"]);
+
+        // Narrowed to one target, links in the others do not count
+        assert_eq!(filtered(SkillFilter::Linked, &[&codex]), Vec::<&str>::new());
+        assert_eq!(
+            filtered(SkillFilter::Unlinked, &[&codex]),
+            ["docx", "pdf", "zip"]
+        );
+        assert_eq!(filtered(SkillFilter::Broken, &[&codex]), ["pdf"]);
+        assert_eq!(
+            filtered(SkillFilter::Broken, &[&claude]),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let filtered = [1, 4, 6, 9];
+        assert_eq!(step(&[], 0, 1), None);
+
+        // Within the list
+        assert_eq!(step(&filtered, 4, 0), Some(4));
+        assert_eq!(step(&filtered, 4, 1), Some(6));
+        assert_eq!(step(&filtered, 4, -1), Some(1));
+        assert_eq!(step(&filtered, 4, 10), Some(9));
+        assert_eq!(step(&filtered, 4, -10), Some(1));
+
+        // A filtered-out selection settles on the nearest row after it
+        assert_eq!(step(&filtered, 5, 0), Some(6));
+        assert_eq!(step(&filtered, 0, 0), Some(1));
+        assert_eq!(step(&filtered, 10, 0), Some(9));
+
+        // Stepping down lands on that row, stepping up on the row before it
+        assert_eq!(step(&filtered, 5, 1), Some(6));
+        assert_eq!(step(&filtered, 5, 2), Some(9));
+        assert_eq!(step(&filtered, 5, -1), Some(4));
+        assert_eq!(step(&filtered, 5, -2), Some(1));
+        assert_eq!(step(&filtered, 10, -1), Some(9));
+    }
+}
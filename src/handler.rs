@@ -1,6 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::app::{App, View};
+use crate::plugin::Repair;
 
 /// Handle a key event and update app state.
 pub fn handle_key(app: &mut App, key: KeyEvent) {
@@ -9,6 +10,13 @@ pub fn handle_key(app: &mut App, key: KeyEvent) {
         View::SkillList => handle_skill_list_key(app, key),
         View::LinkTargetSelect => handle_link_target_key(app, key),
         View::InstallInput => handle_install_input_key(app, key),
+        View::UpdatePreview => handle_update_preview_key(app, key),
+        View::SkillDetail => handle_skill_detail_key(app, key),
+        View::Health => handle_health_key(app, key),
+        View::Available => handle_available_key(app, key),
+        View::MarketplaceInput => handle_marketplace_input_key(app, key),
+        View::GlobalSearch => handle_global_search_key(app, key),
+        View::AllSkills => handle_all_skills_key(app, key),
     }
 }
 
@@ -30,7 +38,39 @@ fn handle_plugin_list_key(app: &mut App, key: KeyEvent) {
         (KeyCode::Char('d'), _) => app.delete_selected(),
         (KeyCode::Char('r'), _) => app.refresh(),
         (KeyCode::Char('u'), _) => app.update_selected(),
+        (KeyCode::Char('U'), _) => app.update_all(),
+        (KeyCode::Char('b'), _) => app.rollback_selected(),
+        (KeyCode::Char('H'), _) => app.enter_health_view(),
+        (KeyCode::Char('A'), _) => app.enter_all_skills_view(),
+        (KeyCode::Tab, _) => app.enter_available_view(),
         (KeyCode::Char('/'), _) => app.enter_search(),
+        (KeyCode::Char('S'), _) => app.enter_global_search(),
+        _ => {}
+    }
+}
+
+/// Handle keys in the Available view.
+fn handle_available_key(app: &mut App, key: KeyEvent) {
+    if app.search_active {
+        handle_search_input(app, key);
+        return;
+    }
+
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('q'), _) => app.should_quit = true,
+        (KeyCode::Tab, _) | (KeyCode::Esc, _) | (KeyCode::Char('h'), _) => {
+            app.back_to_plugin_list()
+        }
+        (KeyCode::Char('j'), _) | (KeyCode::Down, _) => app.select_next(),
+        (KeyCode::Char('k'), _) | (KeyCode::Up, _) => app.select_prev(),
+        (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.scroll_down(),
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => app.scroll_up(),
+        (KeyCode::Char('i'), _) | (KeyCode::Enter, _) => app.install_selected_entry(),
+        (KeyCode::Char('a'), _) => app.enter_marketplace_input(),
+        (KeyCode::Char('u'), _) => app.update_marketplaces(),
+        (KeyCode::Char('r'), _) => app.load_marketplaces(),
+        (KeyCode::Char('/'), _) => app.enter_search(),
+        (KeyCode::Char('S'), _) => app.enter_global_search(),
         _ => {}
     }
 }
@@ -44,14 +84,51 @@ fn handle_skill_list_key(app: &mut App, key: KeyEvent) {
 
     match (key.code, key.modifiers) {
         (KeyCode::Char('q'), _) => app.should_quit = true,
+        (KeyCode::Esc, _) if app.has_marks() => app.clear_marks(),
         (KeyCode::Esc, _) | (KeyCode::Char('h'), _) => app.back_to_plugin_list(),
         (KeyCode::Char('j'), _) | (KeyCode::Down, _) => app.select_next(),
         (KeyCode::Char('k'), _) | (KeyCode::Up, _) => app.select_prev(),
         (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.scroll_down(),
         (KeyCode::Char('u'), KeyModifiers::CONTROL) => app.scroll_up(),
+        (KeyCode::Char(' '), _) => app.toggle_mark(),
+        (KeyCode::Char('v'), _) => app.toggle_range_mark(),
+        (KeyCode::Char('a'), _) => app.mark_all_filtered(),
         (KeyCode::Char('l'), _) | (KeyCode::Enter, _) => app.enter_link_target_view(),
         (KeyCode::Char('L'), _) => app.link_to_all_targets(),
+        (KeyCode::Char('U'), _) => app.unlink_from_all_targets(),
+        (KeyCode::Char('i'), _) => app.enter_skill_detail(),
+        (KeyCode::Char('p'), _) => app.toggle_skill_preview(),
+        (KeyCode::Char('J'), _) => app.scroll_skill_preview(1),
+        (KeyCode::Char('K'), _) => app.scroll_skill_preview(-1),
+        (KeyCode::Tab, _) => app.switch_tab(true),
+        (KeyCode::BackTab, _) => app.switch_tab(false),
+        (KeyCode::Char('/'), _) => app.enter_search(),
+        (KeyCode::Char('S'), _) => app.enter_global_search(),
+        _ => {}
+    }
+}
+
+/// Handle keys in the all-skills view.
+fn handle_all_skills_key(app: &mut App, key: KeyEvent) {
+    if app.search_active {
+        handle_search_input(app, key);
+        return;
+    }
+
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('q'), _) => app.should_quit = true,
+        (KeyCode::Esc, _) | (KeyCode::Char('h'), _) => app.back_to_plugin_list(),
+        (KeyCode::Char('j'), _) | (KeyCode::Down, _) => app.select_next(),
+        (KeyCode::Char('k'), _) | (KeyCode::Up, _) => app.select_prev(),
+        (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.scroll_down(),
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => app.scroll_up(),
+        (KeyCode::Char('l'), _) => app.link_selected_all_skill(),
+        (KeyCode::Char('u'), _) => app.unlink_selected_all_skill(),
+        (KeyCode::Char('f'), _) => app.cycle_all_skills_filter(),
+        (KeyCode::Char('t'), _) => app.cycle_all_skills_target(),
+        (KeyCode::Char('r'), _) => app.refresh(),
         (KeyCode::Char('/'), _) => app.enter_search(),
+        (KeyCode::Char('S'), _) => app.enter_global_search(),
         _ => {}
     }
 }
@@ -64,6 +141,35 @@ fn handle_link_target_key(app: &mut App, key: KeyEvent) {
         KeyCode::Char('j') | KeyCode::Down => app.select_next(),
         KeyCode::Char('k') | KeyCode::Up => app.select_prev(),
         KeyCode::Char('l') | KeyCode::Enter => app.toggle_selected_link_target(),
+        KeyCode::Char('u') => app.unlink_from_selected_target(),
+        KeyCode::Char('c') => app.copy_to_selected_target(),
+        _ => {}
+    }
+}
+
+/// Handle keys in the link health view.
+fn handle_health_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Esc | KeyCode::Char('h') => app.back_to_plugin_list(),
+        KeyCode::Char('j') | KeyCode::Down => app.select_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_prev(),
+        KeyCode::Char('f') | KeyCode::Enter => app.repair_selected(None),
+        KeyCode::Char('l') => app.repair_selected(Some(Repair::Relink)),
+        KeyCode::Char('d') => app.repair_selected(Some(Repair::Remove)),
+        KeyCode::Char('a') => app.repair_selected(Some(Repair::Adopt)),
+        KeyCode::Char('m') => app.repair_selected(Some(Repair::Replace)),
+        KeyCode::Char('A') => app.adopt_all(),
+        KeyCode::Char('r') => app.scan_health(),
+        _ => {}
+    }
+}
+
+/// Handle keys in the skill detail view.
+fn handle_skill_detail_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Esc | KeyCode::Char('h') | KeyCode::Char('i') => app.back_to_skill_list(),
         _ => {}
     }
 }
@@ -87,8 +193,61 @@ fn handle_install_input_key(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Handle keys in the marketplace input view.
+fn handle_marketplace_input_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.back_to_available(),
+        KeyCode::Enter => app.start_add_marketplace(),
+        KeyCode::Backspace => {
+            if app.input.is_empty() {
+                app.back_to_available();
+            } else {
+                app.input.pop();
+            }
+        }
+        KeyCode::Char(c) => app.input.push(c),
+        _ => {}
+    }
+}
+
+/// Handle keys in global search, where typing edits the query.
+fn handle_global_search_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.back_to_plugin_list(),
+        KeyCode::Enter => app.open_global_result(),
+        KeyCode::Up => app.select_prev(),
+        KeyCode::Down => app.select_next(),
+        KeyCode::Backspace => app.global_search_backspace(),
+        KeyCode::Char(c) => app.global_search_input(c),
+        _ => {}
+    }
+}
+
+/// Handle keys in the update preview view.
+fn handle_update_preview_key(app: &mut App, key: KeyEvent) {
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('q'), _) => app.should_quit = true,
+        (KeyCode::Char('j'), _) | (KeyCode::Down, _) => app.scroll_preview(1),
+        (KeyCode::Char('k'), _) | (KeyCode::Up, _) => app.scroll_preview(-1),
+        (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.scroll_preview(10),
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => app.scroll_preview(-10),
+        (KeyCode::Char('y'), _) | (KeyCode::Enter, _) => app.accept_update(),
+        (KeyCode::Char('n'), _) | (KeyCode::Esc, _) => app.reject_update(),
+        _ => {}
+    }
+}
+
 /// Handle keys in search mode.
 fn handle_search_input(app: &mut App, key: KeyEvent) {
+    // Mark every match without leaving the search
+    if app.view == View::SkillList
+        && key.code == KeyCode::Char('a')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+    {
+        app.mark_all_filtered();
+        return;
+    }
+
     match key.code {
         KeyCode::Esc => {
             app.exit_search();
@@ -99,7 +258,15 @@ fn handle_search_input(app: &mut App, key: KeyEvent) {
             match app.view {
                 View::PluginList => app.enter_skill_list(),
                 View::SkillList => app.enter_link_target_view(),
-                View::LinkTargetSelect | View::InstallInput => {}
+                View::LinkTargetSelect
+                | View::InstallInput
+                | View::UpdatePreview
+                | View::SkillDetail
+                | View::Health
+                | View::Available
+                | View::MarketplaceInput
+                | View::GlobalSearch
+                | View::AllSkills => {}
             }
         }
         KeyCode::Backspace => {
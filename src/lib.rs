@@ -1,5 +1,7 @@
+pub mod export;
+pub mod manifest;
 pub mod plugin;
 pub mod status;
 
-pub use plugin::{GitSource, LinkTarget, Plugin, PluginError, PluginManager, Skill};
+pub use plugin::{GitRef, GitSource, LinkTarget, Plugin, PluginError, PluginManager, Skill};
 pub use status::{StatusKind, StatusManager};
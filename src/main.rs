@@ -1,22 +1,73 @@
 use std::io::{self, stdout};
+use std::process::ExitCode;
 use std::time::Duration;
 
+use clap::Parser;
 use crossterm::{
-    event::{self, Event, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
+    event::{self, Event, KeyEventKind},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
+use skir::plugin::{LinkTarget, Settings};
+use skir::{export, manifest, plugin, status};
 
 mod app;
+mod cli;
+mod filter;
 mod handler;
-mod plugin;
-mod status;
+mod marks;
 mod ui;
 
 use app::App;
+use cli::Cli;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // Offer the targets of the project we are in, if any
+    let settings = match &cli.project {
+        Some(dir) => match dir.canonicalize() {
+            Ok(dir) => Settings::load(Some(&dir)),
+            Err(e) => {
+                eprintln!("error: {}: {}", dir.display(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Settings::load(None).and_then(|settings| {
+            let project = std::env::current_dir()
+                .ok()
+                .and_then(|dir| LinkTarget::detect_project(&dir, &settings.cache_dir.value));
+            match project {
+                Some(project) => Settings::load(Some(&project)),
+                None => Ok(settings),
+            }
+        }),
+    };
+    match settings {
+        Ok(settings) => Settings::init(settings),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // Run headless when a subcommand is given
+    if let Some(command) = cli.command {
+        return cli::run(command);
+    }
+
+    match run_tui() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-fn main() -> io::Result<()> {
+/// Run the interactive TUI until the user quits.
+fn run_tui() -> io::Result<()> {
     // Set up panic hook to restore terminal on panic
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -37,6 +88,12 @@ fn main() -> io::Result<()> {
     while !app.should_quit {
         app.poll_installs();
         app.poll_updates();
+        app.poll_fetches();
+        app.poll_rollbacks();
+        app.poll_update_checks();
+        app.poll_update_all();
+        app.poll_marketplaces();
+        app.poll_repairs();
         app.status.clear_expired();
         terminal.draw(|frame| ui::draw(frame, &mut app))?;
 
@@ -0,0 +1,881 @@
+//! Declarative manifest (`skir.toml`) and reconciliation against installed plugins.
+//!
+//! A manifest lists the plugins that should be installed and which of their
+//! skills should be linked to which targets:
+//!
+//! ```toml
+//! [[plugins]]
+//! source = "anthropics/skills"
+//! branch = "main"
+//!
+//! [plugins.link]
+//! pdf = ["claude", "codex"]
+//! docx = ["claude"]
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::plugin::{
+    DEFAULT_HOST, GitRef, GitSource, LOCKFILE_NAME, LinkTarget, Lockfile, Plugin, PluginError,
+    PluginManager,
+};
+
+/// Default manifest file name.
+pub const MANIFEST_FILE: &str = "skir.toml";
+
+/// A parsed manifest file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Plugins that should be installed.
+    #[serde(default)]
+    pub plugins: Vec<ManifestPlugin>,
+}
+
+/// A plugin entry in the manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestPlugin {
+    /// Git URL or `owner/repo` shorthand, optionally with a `#branch` or `@ref` suffix.
+    pub source: String,
+    /// Optional tag or commit to pin, like an `@ref` suffix.
+    #[serde(rename = "ref")]
+    pub reference: Option<String>,
+    /// Optional branch to follow, like a `#branch` suffix.
+    pub branch: Option<String>,
+    /// Optional subdirectory to install, like a `//path` suffix.
+    pub subdir: Option<String>,
+    /// Skill name to the ids of the targets it should be linked to.
+    #[serde(default)]
+    pub link: BTreeMap<String, Vec<String>>,
+}
+
+impl Manifest {
+    /// Load a manifest from a file, whose links may only name `targets`.
+    pub fn load(path: &Path, targets: &[LinkTarget]) -> Result<Self, PluginError> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content, path, targets)
+    }
+
+    /// Parse manifest content. `path` is only used for error messages.
+    pub fn parse(content: &str, path: &Path, targets: &[LinkTarget]) -> Result<Self, PluginError> {
+        let manifest: Manifest =
+            toml::from_str(content).map_err(|e| PluginError::InvalidManifest {
+                path: path.to_path_buf(),
+                reason: e.message().to_string(),
+            })?;
+
+        // Validate sources and targets up front so a plan never fails halfway
+        for entry in &manifest.plugins {
+            entry
+                .git_source()
+                .map_err(|e| PluginError::InvalidManifest {
+                    path: path.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+            for target in entry.link.values().flatten() {
+                if !targets.iter().any(|t| t.id() == target) {
+                    return Err(PluginError::InvalidManifest {
+                        path: path.to_path_buf(),
+                        reason: format!("unknown link target: {}", target),
+                    });
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl ManifestPlugin {
+    /// Parse the source into a `GitSource`, carrying over the ref.
+    pub fn git_source(&self) -> Result<GitSource, PluginError> {
+        self.git_source_with_host(DEFAULT_HOST)
+    }
+
+    /// Parse the source like [`ManifestPlugin::git_source`], with `owner/repo` shorthands
+    /// on `host`.
+    pub fn git_source_with_host(&self, host: &str) -> Result<GitSource, PluginError> {
+        let mut source = GitSource::parse_with_host(&self.source, host)?;
+        let reference = match (&self.reference, &self.branch) {
+            (Some(pin), None) => Some(GitRef::parse_pin(pin)),
+            (None, Some(branch)) => Some(GitRef::Branch(branch.clone())),
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                return Err(PluginError::InvalidUrl {
+                    url: format!("{} (both ref and branch given)", self.source),
+                });
+            }
+        };
+
+        if reference.is_some() {
+            if source.reference.is_some() {
+                return Err(PluginError::InvalidUrl {
+                    url: format!("{} (ref given twice)", self.source),
+                });
+            }
+            source.reference = reference;
+        }
+
+        if let Some(subdir) = &self.subdir {
+            source.set_subdir(subdir)?;
+        }
+        Ok(source)
+    }
+}
+
+/// A single step needed to bring the installed state in line with a manifest.
+#[derive(Debug, Clone)]
+pub enum SyncAction {
+    /// Clone a plugin that is listed but not installed, optionally at a locked commit.
+    Install {
+        source: GitSource,
+        commit: Option<String>,
+    },
+    /// Move an installed plugin to its locked commit.
+    Checkout { plugin: Arc<Plugin>, commit: String },
+    /// Clone an installed plugin again at the ref the manifest now asks for,
+    /// optionally then checking out a locked commit.
+    SwitchRef {
+        plugin: Arc<Plugin>,
+        reference: Option<GitRef>,
+        commit: Option<String>,
+    },
+    /// Remove an installed plugin that is not listed (only when pruning).
+    Remove { plugin: Arc<Plugin> },
+    /// Link a skill to a target.
+    Link {
+        qualified_name: String,
+        target: LinkTarget,
+    },
+    /// Unlink a skill from a target.
+    Unlink {
+        qualified_name: String,
+        target: LinkTarget,
+    },
+}
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncAction::Install {
+                source,
+                commit: None,
+            } => write!(f, "install {}/{}", source.owner, source.name()),
+            SyncAction::Install {
+                source,
+                commit: Some(commit),
+            } => write!(
+                f,
+                "install {}/{} @ {}",
+                source.owner,
+                source.name(),
+                short_commit(commit)
+            ),
+            SyncAction::Checkout { plugin, commit } => {
+                write!(
+                    f,
+                    "checkout {}/{} @ {}",
+                    plugin.owner,
+                    plugin.name(),
+                    short_commit(commit)
+                )
+            }
+            SyncAction::SwitchRef {
+                plugin,
+                reference,
+                commit,
+            } => {
+                let reference = reference
+                    .as_ref()
+                    .map_or_else(|| "default branch".to_string(), GitRef::to_string);
+                write!(
+                    f,
+                    "switch  {}/{} -> {}",
+                    plugin.owner,
+                    plugin.name(),
+                    reference
+                )?;
+                match commit {
+                    Some(commit) => write!(f, " @ {}", short_commit(commit)),
+                    None => Ok(()),
+                }
+            }
+            SyncAction::Remove { plugin } => {
+                write!(f, "remove  {}/{}", plugin.owner, plugin.name())
+            }
+            SyncAction::Link {
+                qualified_name,
+                target,
+            } => {
+                write!(f, "link    {} -> {}", qualified_name, target.id())
+            }
+            SyncAction::Unlink {
+                qualified_name,
+                target,
+            } => {
+                write!(f, "unlink  {} -> {}", qualified_name, target.id())
+            }
+        }
+    }
+}
+
+/// Abbreviate a commit SHA for display.
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// Options controlling how a plan is computed.
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Remove installed plugins that are not listed.
+    pub prune: bool,
+    /// When set, pin every listed plugin to the commit recorded here.
+    pub lockfile: Option<Lockfile>,
+    /// The host of `owner/repo` shorthands; GitHub unless set.
+    pub default_host: Option<String>,
+    /// The targets skills are linked to and unlinked from.
+    pub targets: Vec<LinkTarget>,
+}
+
+impl SyncOptions {
+    /// Look up one of the targets by its identifier.
+    fn target(&self, id: &str) -> Option<&LinkTarget> {
+        self.targets.iter().find(|t| t.id() == id)
+    }
+}
+
+/// An ordered list of actions produced by [`plan`].
+#[derive(Debug, Clone, Default)]
+pub struct SyncPlan {
+    pub actions: Vec<SyncAction>,
+    /// Problems found while planning that do not block the rest of the plan.
+    pub warnings: Vec<String>,
+}
+
+impl SyncPlan {
+    /// Check if there is nothing to do.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Compute the actions needed to reconcile installed plugins with a manifest.
+///
+/// Plugins not listed in the manifest are left alone unless pruning. In
+/// locked mode every listed plugin must have a lockfile entry.
+pub fn plan(
+    manifest: &Manifest,
+    installed: &[Arc<Plugin>],
+    options: &SyncOptions,
+) -> Result<SyncPlan, PluginError> {
+    let mut warnings = Vec::new();
+    let mut removes = Vec::new();
+    let mut installs = Vec::new();
+    let mut links = Vec::new();
+    let mut listed = HashSet::new();
+
+    let host = options.default_host.as_deref().unwrap_or(DEFAULT_HOST);
+    for entry in &manifest.plugins {
+        let source = entry.git_source_with_host(host)?;
+        listed.insert((source.host.clone(), source.owner.clone(), source.name()));
+
+        let locked_commit = match &options.lockfile {
+            Some(lockfile) => {
+                let locked = lockfile
+                    .get(&source.host, &source.owner, &source.name())
+                    .ok_or_else(|| PluginError::NotLocked {
+                        name: format!("{}/{}", source.owner, source.name()),
+                    })?;
+                Some(locked.commit.clone())
+            }
+            None => None,
+        };
+
+        let plugin = installed.iter().find(|p| {
+            p.host == source.host && p.owner == source.owner && p.name() == source.name()
+        });
+
+        let Some(plugin) = plugin else {
+            // Not installed yet: install, then link everything requested
+            for (skill, targets) in &entry.link {
+                for target in targets.iter().filter_map(|t| options.target(t)) {
+                    links.push(SyncAction::Link {
+                        qualified_name: format!("{}:{}:{}", source.owner, source.name(), skill),
+                        target: target.clone(),
+                    });
+                }
+            }
+            installs.push(SyncAction::Install {
+                source,
+                commit: locked_commit,
+            });
+            continue;
+        };
+
+        if !plugin.is_at_ref(source.reference.as_ref()) {
+            // The manifest asks for another branch, tag or commit
+            installs.push(SyncAction::SwitchRef {
+                plugin: Arc::clone(plugin),
+                reference: source.reference.clone(),
+                commit: locked_commit,
+            });
+        } else if let Some(commit) = locked_commit
+            && plugin.head_commit().as_deref() != Some(commit.as_str())
+        {
+            installs.push(SyncAction::Checkout {
+                plugin: Arc::clone(plugin),
+                commit,
+            });
+        }
+
+        for name in entry.link.keys() {
+            if !plugin.skills().iter().any(|s| &s.name == name) {
+                warnings.push(format!(
+                    "{}/{} has no skill named {}",
+                    plugin.owner,
+                    plugin.name(),
+                    name
+                ));
+            }
+        }
+
+        for skill in plugin.skills() {
+            let wanted: Vec<&LinkTarget> = entry
+                .link
+                .get(&skill.name)
+                .map(|ids| ids.iter().filter_map(|t| options.target(t)).collect())
+                .unwrap_or_default();
+
+            for target in &options.targets {
+                let linked = skill.is_linked_to(target);
+                let want = wanted.contains(&target);
+                if want && !linked {
+                    links.push(SyncAction::Link {
+                        qualified_name: skill.qualified_name(),
+                        target: target.clone(),
+                    });
+                } else if !want && linked {
+                    links.push(SyncAction::Unlink {
+                        qualified_name: skill.qualified_name(),
+                        target: target.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if options.prune {
+        // Local plugins cannot be listed in a manifest, so they are never pruned
+        for plugin in installed.iter().filter(|p| !p.is_local()) {
+            let key = (
+                plugin.host.clone(),
+                plugin.owner.clone(),
+                plugin.name().into_owned(),
+            );
+            if !listed.contains(&key) {
+                removes.push(SyncAction::Remove {
+                    plugin: Arc::clone(plugin),
+                });
+            }
+        }
+    }
+
+    // Removals and installs first, so links see the final set of plugins
+    let mut actions = removes;
+    actions.extend(installs);
+    actions.extend(links);
+    Ok(SyncPlan { actions, warnings })
+}
+
+/// The lockfile kept next to a manifest.
+pub fn lockfile_path(manifest: &Path) -> PathBuf {
+    manifest.with_file_name(LOCKFILE_NAME)
+}
+
+/// Lockfile keys of the plugins a manifest lists, with `owner/repo` shorthands on `host`.
+pub fn lock_keys(manifest: &Manifest, host: &str) -> HashSet<String> {
+    manifest
+        .plugins
+        .iter()
+        .filter_map(|entry| entry.git_source_with_host(host).ok())
+        .map(|source| Lockfile::key(&source.host, &source.owner, &source.name()))
+        .collect()
+}
+
+/// Record commits of the plugins a manifest lists in the lockfile next to it.
+///
+/// Plugins the manifest does not list stay in the cache lockfile.
+pub fn with_manifest_lockfile(
+    manager: PluginManager,
+    path: &Path,
+) -> Result<PluginManager, PluginError> {
+    let manifest = Manifest::load(path, manager.targets())?;
+    let keys = lock_keys(&manifest, manager.default_host());
+    Ok(manager.with_project_lockfile(lockfile_path(path), keys))
+}
+
+/// Record commits in the lockfile of the manifest in the current directory,
+/// if there is one, so installs and updates keep it in step with `skir sync`.
+///
+/// A manifest that does not parse is left for `skir sync` to report.
+pub fn with_project_lockfile(manager: PluginManager) -> PluginManager {
+    let manifest = Path::new(MANIFEST_FILE);
+    if !manifest.is_file() {
+        return manager;
+    }
+    with_manifest_lockfile(manager.clone(), manifest).unwrap_or(manager)
+}
+
+/// Apply a sync plan, continuing past individual failures.
+///
+/// Returns the actions that failed together with their errors.
+pub fn apply(plan: &SyncPlan, manager: &PluginManager) -> Vec<(SyncAction, PluginError)> {
+    let mut failures = Vec::new();
+    // Installed plugins, rescanned lazily after installs and removals
+    let mut installed: Option<Vec<Arc<Plugin>>> = None;
+
+    for action in &plan.actions {
+        let result = match action {
+            SyncAction::Install { source, commit } => {
+                installed = None;
+                match commit {
+                    Some(commit) => manager.install_pinned(source.clone(), commit),
+                    None => manager.install_source(source.clone()),
+                }
+                .map(|_| ())
+            }
+            SyncAction::Checkout { plugin, commit } => {
+                installed = None;
+                manager.checkout(plugin, commit).map(|_| ())
+            }
+            SyncAction::SwitchRef {
+                plugin,
+                reference,
+                commit,
+            } => {
+                installed = None;
+                manager
+                    .switch_ref(plugin, reference.as_ref())
+                    .and_then(|switched| match commit {
+                        Some(commit) => manager.checkout(&switched, commit).map(|_| ()),
+                        None => Ok(()),
+                    })
+            }
+            SyncAction::Remove { plugin } => {
+                installed = None;
+                manager.remove(plugin)
+            }
+            SyncAction::Link {
+                qualified_name,
+                target,
+            }
+            | SyncAction::Unlink {
+                qualified_name,
+                target,
+            } => {
+                let link = matches!(action, SyncAction::Link { .. });
+                let plugins = match installed.take() {
+                    Some(plugins) => Ok(plugins),
+                    None => manager.list_installed(),
+                };
+                plugins.and_then(|plugins| {
+                    let result = apply_link(&plugins, qualified_name, target, link);
+                    installed = Some(plugins);
+                    result
+                })
+            }
+        };
+
+        if let Err(e) = result {
+            failures.push((action.clone(), e));
+        }
+    }
+
+    failures
+}
+
+/// Record the current commit of every listed, installed plugin in the manager's lockfile.
+pub fn lock(manifest: &Manifest, manager: &PluginManager) -> Result<(), PluginError> {
+    let installed = manager.list_installed()?;
+    for entry in &manifest.plugins {
+        let source = entry.git_source_with_host(manager.default_host())?;
+        if let Some(plugin) = installed
+            .iter()
+            .find(|p| p.host == source.host && p.owner == source.owner && p.name() == source.name())
+        {
+            manager.record_lock(plugin)?;
+        }
+    }
+    Ok(())
+}
+
+/// Link or unlink a skill found by qualified name.
+fn apply_link(
+    installed: &[Arc<Plugin>],
+    qualified_name: &str,
+    target: &LinkTarget,
+    link: bool,
+) -> Result<(), PluginError> {
+    let skill = installed
+        .iter()
+        .flat_map(|p| p.skills())
+        .find(|s| s.qualified_name() == qualified_name)
+        .ok_or_else(|| PluginError::SkillNotFound {
+            name: qualified_name.to_string(),
+        })?;
+
+    if link {
+        skill.link_to(target)
+    } else {
+        skill.unlink_from(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::LockedPlugin;
+    use crate::plugin::testing::{commit_all, git, init_bare, push, repo_source, target, targets};
+    use tempfile::tempdir;
+
+    const MANIFEST: &str = r#"
+[[plugins]]
+source = "anthropics/skills"
+ref = "v1.0"
+
+[plugins.link]
+pdf = ["claude", "codex"]
+
+[[plugins]]
+source = "https://gitlab.com/user/project"
+branch = "develop"
+"#;
+
+    fn build_plugin(owner: &str, repo: &str, root: &Path) -> Arc<Plugin> {
+        Arc::new(
+            Plugin::build(
+                "github.com".to_string(),
+                owner.to_string(),
+                repo.to_string(),
+                root.to_path_buf(),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let dir = tempdir().unwrap();
+        let manifest =
+            Manifest::parse(MANIFEST, Path::new("skir.toml"), &targets(dir.path())).unwrap();
+        assert_eq!(manifest.plugins.len(), 2);
+        assert_eq!(manifest.plugins[0].reference.as_deref(), Some("v1.0"));
+        assert_eq!(manifest.plugins[1].branch.as_deref(), Some("develop"));
+        assert_eq!(manifest.plugins[0].link["pdf"], vec!["claude", "codex"]);
+        assert!(manifest.plugins[1].link.is_empty());
+
+        let source = manifest.plugins[0].git_source().unwrap();
+        assert_eq!(source.owner, "anthropics");
+        assert_eq!(source.reference, Some(GitRef::Tag("v1.0".to_string())));
+        let source = manifest.plugins[1].git_source().unwrap();
+        assert_eq!(
+            source.reference,
+            Some(GitRef::Branch("develop".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_manifest_unknown_target() {
+        let dir = tempdir().unwrap();
+        let content = "[[plugins]]\nsource = \"a/b\"\n[plugins.link]\nfoo = [\"emacs\"]\n";
+        assert!(matches!(
+            Manifest::parse(content, Path::new("skir.toml"), &targets(dir.path())),
+            Err(PluginError::InvalidManifest { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_manifest_conflicting_refs() {
+        let content = "[[plugins]]\nsource = \"a/b@v1\"\nbranch = \"main\"\n";
+        assert!(Manifest::parse(content, Path::new("skir.toml"), &[]).is_err());
+        let content = "[[plugins]]\nsource = \"a/b\"\nref = \"v1\"\nbranch = \"main\"\n";
+        assert!(Manifest::parse(content, Path::new("skir.toml"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_manifest_subdir() {
+        let content = "[[plugins]]\nsource = \"a/b\"\nsubdir = \"/skills/\"\n";
+        let manifest = Manifest::parse(content, Path::new("skir.toml"), &[]).unwrap();
+        let source = manifest.plugins[0].git_source().unwrap();
+        assert_eq!(source.subdir.as_deref(), Some("skills"));
+        assert_eq!(source.name(), "b--skills");
+
+        let content = "[[plugins]]\nsource = \"a/b//x\"\nsubdir = \"y\"\n";
+        assert!(Manifest::parse(content, Path::new("skir.toml"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_manifest_invalid_source() {
+        let content = "[[plugins]]\nsource = \"not-a-url\"\n";
+        assert!(Manifest::parse(content, Path::new("skir.toml"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_plan_installs_missing_and_links() {
+        let dir = tempdir().unwrap();
+        let options = SyncOptions {
+            targets: targets(dir.path()),
+            ..Default::default()
+        };
+        let manifest = Manifest::parse(MANIFEST, Path::new("skir.toml"), &options.targets).unwrap();
+        let plan = plan(&manifest, &[], &options).unwrap();
+
+        let installs = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a, SyncAction::Install { .. }))
+            .count();
+        assert_eq!(installs, 2);
+
+        // Installs come before links
+        assert!(matches!(plan.actions[0], SyncAction::Install { .. }));
+        assert!(matches!(plan.actions[1], SyncAction::Install { .. }));
+        let links: Vec<String> = plan.actions[2..].iter().map(|a| a.to_string()).collect();
+        assert_eq!(
+            links,
+            vec![
+                "link    anthropics:skills:pdf -> claude",
+                "link    anthropics:skills:pdf -> codex",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_prune() {
+        let dir = tempdir().unwrap();
+        let listed = build_plugin("anthropics", "skills", dir.path());
+        let extra = build_plugin("someone", "extra", dir.path());
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"anthropics/skills\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+        let installed = vec![listed, extra];
+
+        let plan_keep = plan(&manifest, &installed, &SyncOptions::default()).unwrap();
+        assert!(plan_keep.is_empty());
+
+        let options = SyncOptions {
+            prune: true,
+            ..Default::default()
+        };
+        let plan_prune = plan(&manifest, &installed, &options).unwrap();
+        assert_eq!(plan_prune.actions.len(), 1);
+        assert_eq!(plan_prune.actions[0].to_string(), "remove  someone/extra");
+    }
+
+    #[test]
+    fn test_plan_locked() {
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"anthropics/skills\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+
+        let mut lockfile = Lockfile::default();
+        let options = SyncOptions {
+            prune: false,
+            lockfile: Some(lockfile.clone()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            plan(&manifest, &[], &options),
+            Err(PluginError::NotLocked { .. })
+        ));
+
+        lockfile.plugins.insert(
+            Lockfile::key("github.com", "anthropics", "skills"),
+            LockedPlugin {
+                url: "https://github.com/anthropics/skills".to_string(),
+                commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            },
+        );
+        let options = SyncOptions {
+            prune: false,
+            lockfile: Some(lockfile),
+            ..Default::default()
+        };
+        let plan = plan(&manifest, &[], &options).unwrap();
+        assert_eq!(
+            plan.actions[0].to_string(),
+            "install anthropics/skills @ 0123456789ab"
+        );
+    }
+
+    #[test]
+    fn test_plan_switches_changed_ref() {
+        let dir = tempdir().unwrap();
+        let installed = vec![build_plugin("anthropics", "skills", dir.path())];
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"anthropics/skills\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+        assert!(
+            plan(&manifest, &installed, &SyncOptions::default())
+                .unwrap()
+                .is_empty()
+        );
+
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"anthropics/skills\"\nref = \"v1.2\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+        let plan = plan(&manifest, &installed, &SyncOptions::default()).unwrap();
+        assert_eq!(plan.actions.len(), 1);
+        assert_eq!(
+            plan.actions[0].to_string(),
+            "switch  anthropics/skills -> @v1.2"
+        );
+    }
+
+    #[test]
+    fn test_sync_switches_ref() {
+        let dir = tempdir().unwrap();
+        let work = dir.path().join("work");
+        let bare = dir.path().join("remote/skills");
+        let first = init_bare(&work, &bare);
+        git(&work, &["tag", "v1.1"]);
+        let second = commit_all(&work, "second");
+        git(&work, &["tag", "v1.2"]);
+        git(
+            &work,
+            &["push", "--quiet", "origin", "main", "v1.1", "v1.2"],
+        );
+
+        let manager = PluginManager::with_cache_dir(dir.path().join("cache"));
+        let v1 = Some(GitRef::Tag("v1.1".to_string()));
+        let plugin = manager.install_source(repo_source(&bare, v1)).unwrap();
+        assert_eq!(plugin.head_commit().as_deref(), Some(first.as_str()));
+        let target = target(&dir.path().join("target"));
+        let manager = manager.with_targets(vec![target.clone()]);
+        plugin.skills()[0].link_to(&target).unwrap();
+
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"me/skills\"\nref = \"v1.2\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+        let sync_plan = plan(
+            &manifest,
+            &manager.list_installed().unwrap(),
+            &SyncOptions::default(),
+        )
+        .unwrap();
+        assert!(matches!(sync_plan.actions[0], SyncAction::SwitchRef { .. }));
+        assert!(apply(&sync_plan, &manager).is_empty());
+
+        let installed = manager.list_installed().unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(
+            installed[0].reference,
+            Some(GitRef::Tag("v1.2".to_string()))
+        );
+        assert_eq!(installed[0].head_commit().as_deref(), Some(second.as_str()));
+        assert_eq!(installed[0].history(), vec![first]);
+        assert!(installed[0].skills()[0].is_linked_to(&target));
+    }
+
+    #[test]
+    fn test_plan_warns_on_missing_skill() {
+        let dir = tempdir().unwrap();
+        let installed = vec![build_plugin("anthropics", "skills", dir.path())];
+        let options = SyncOptions {
+            targets: targets(&dir.path().join("targets")),
+            ..Default::default()
+        };
+        let manifest = Manifest::parse(MANIFEST, Path::new("skir.toml"), &options.targets).unwrap();
+
+        let plan = plan(&manifest, &installed, &options).unwrap();
+        assert_eq!(
+            plan.warnings,
+            vec!["anthropics/skills has no skill named pdf"]
+        );
+    }
+
+    #[test]
+    fn test_sync_locked_checkout() {
+        let dir = tempdir().unwrap();
+        let work = dir.path().join("work");
+        let bare = dir.path().join("remote/skills");
+        let first = init_bare(&work, &bare);
+        let second = commit_all(&work, "second");
+        push(&work);
+
+        let manager = PluginManager::with_cache_dir(dir.path().join("cache"));
+        let plugin = manager.install_source(repo_source(&bare, None)).unwrap();
+        assert_eq!(plugin.head_commit().as_deref(), Some(second.as_str()));
+
+        let manifest = Manifest::parse(
+            "[[plugins]]\nsource = \"me/skills\"\n",
+            Path::new("skir.toml"),
+            &[],
+        )
+        .unwrap();
+        let locked = |commit: &str| {
+            let mut lockfile = Lockfile::default();
+            lockfile.plugins.insert(
+                Lockfile::key("github.com", "me", "skills"),
+                LockedPlugin {
+                    url: format!("file://{}", bare.display()),
+                    commit: commit.to_string(),
+                },
+            );
+            SyncOptions {
+                lockfile: Some(lockfile),
+                ..Default::default()
+            }
+        };
+
+        let sync_plan = plan(
+            &manifest,
+            &manager.list_installed().unwrap(),
+            &locked(&first),
+        )
+        .unwrap();
+        assert_eq!(sync_plan.actions.len(), 1);
+        assert!(matches!(sync_plan.actions[0], SyncAction::Checkout { .. }));
+        assert!(apply(&sync_plan, &manager).is_empty());
+
+        let installed = manager.list_installed().unwrap();
+        assert_eq!(installed[0].head_commit().as_deref(), Some(first.as_str()));
+        assert!(
+            plan(&manifest, &installed, &locked(&first))
+                .unwrap()
+                .is_empty()
+        );
+
+        // A commit that no longer exists upstream fails the checkout
+        let missing = "0123456789abcdef0123456789abcdef01234567";
+        let sync_plan = plan(&manifest, &installed, &locked(missing)).unwrap();
+        let failures = apply(&sync_plan, &manager);
+        assert_eq!(failures.len(), 1);
+        assert!(matches!(
+            failures[0].1,
+            PluginError::PinnedCommitMissing { .. }
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+//! Marked skills in the skill list, and summaries of actions taken on them.
+
+use std::collections::BTreeSet;
+
+use skir::StatusKind;
+
+/// The skills marked in the skill list.
+///
+/// Marks are kept by name rather than index, so they survive rescans that
+/// reorder or remove skills. Methods take the names of the skills shown, the
+/// indices of those matching the search and the index of the selected one.
+#[derive(Debug, Default)]
+pub struct Marks {
+    names: BTreeSet<String>,
+    /// The skill a `v` range selection started at, while one is being made.
+    anchor: Option<String>,
+}
+
+impl Marks {
+    /// Get the indices of the marked skills, including a range being selected.
+    ///
+    /// Only skills matching the search join a range. Marks of skills that are
+    /// gone after a rescan are left out.
+    pub fn indices(&self, skills: &[&str], filtered: &[usize], selected: usize) -> Vec<usize> {
+        let mut marked: BTreeSet<usize> = (0..skills.len())
+            .filter(|&i| self.names.contains(skills[i]))
+            .collect();
+        if let Some(anchor) = self.anchor_index(skills) {
+            let range = anchor.min(selected)..=anchor.max(selected);
+            marked.extend(filtered.iter().filter(|i| range.contains(i)));
+        }
+        marked.into_iter().collect()
+    }
+
+    /// Get the index of the skill a range selection started at.
+    fn anchor_index(&self, skills: &[&str]) -> Option<usize> {
+        let anchor = self.anchor.as_ref()?;
+        skills.iter().position(|s| s == anchor)
+    }
+
+    /// Check if any skill is marked or a range selection is in progress.
+    pub fn any(&self, skills: &[&str]) -> bool {
+        self.anchor_index(skills).is_some() || skills.iter().any(|s| self.names.contains(*s))
+    }
+
+    /// Mark or unmark a skill.
+    pub fn toggle(&mut self, name: &str) {
+        if !self.names.remove(name) {
+            self.names.insert(name.to_string());
+        }
+    }
+
+    /// Start a range selection at the selected skill, or mark the range made so far.
+    pub fn toggle_range(&mut self, skills: &[&str], filtered: &[usize], selected: usize) {
+        let Some(&name) = skills.get(selected) else {
+            return;
+        };
+        match self.anchor {
+            Some(_) => {
+                let indices = self.indices(skills, filtered, selected);
+                self.names
+                    .extend(indices.iter().map(|&i| skills[i].to_string()));
+                self.anchor = None;
+            }
+            None => self.anchor = Some(name.to_string()),
+        }
+    }
+
+    /// Mark every skill matching the search, or unmark them if all are marked.
+    pub fn toggle_all(&mut self, skills: &[&str], filtered: &[usize]) {
+        let filtered: Vec<&str> = filtered
+            .iter()
+            .filter_map(|&i| skills.get(i).copied())
+            .collect();
+        if filtered.iter().all(|name| self.names.contains(*name)) {
+            for name in filtered {
+                self.names.remove(name);
+            }
+        } else {
+            self.names.extend(filtered.iter().map(|s| s.to_string()));
+        }
+    }
+
+    /// Unmark every skill.
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.anchor = None;
+    }
+}
+
+/// Summarize an action on one or more skills in a single status message.
+///
+/// One skill reads like "Linked pdf to Claude Code"; several like
+/// "Linked 3 skills to Claude Code", followed by the ones that failed and
+/// why. `action` names the action in failure messages, e.g. "Link". Returns
+/// `None` if there are no results.
+pub fn report(
+    done: &str,
+    rest: &str,
+    action: &str,
+    results: &[(String, Result<(), String>)],
+) -> Option<(String, StatusKind)> {
+    let failures: Vec<String> = results
+        .iter()
+        .filter_map(|(name, result)| result.as_ref().err().map(|e| format!("{} ({})", name, e)))
+        .collect();
+    let succeeded = results.len() - failures.len();
+
+    let report = match results {
+        [] => return None,
+        [(name, Ok(()))] => (format!("{} {} {}", done, name, rest), StatusKind::Success),
+        [(_, Err(e))] => (format!("{} failed: {}", action, e), StatusKind::Error),
+        _ if failures.is_empty() => (
+            format!("{} {} skills {}", done, succeeded, rest),
+            StatusKind::Success,
+        ),
+        _ => (
+            format!(
+                "{} {} of {} skills {}; {} failed: {}",
+                done,
+                succeeded,
+                results.len(),
+                rest,
+                action,
+                failures.join(", ")
+            ),
+            StatusKind::Error,
+        ),
+    };
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILLS: &[&str] = &["docx", "pdf", "pptx", "xlsx", "zip"];
+
+    #[test]
+    fn test_toggle() {
+        let mut marks = Marks::default();
+        assert!(!marks.any(SKILLS));
+        marks.toggle("pdf");
+        marks.toggle("xlsx");
+        assert_eq!(marks.indices(SKILLS, &[], 0), vec![1, 3]);
+        marks.toggle("pdf");
+        assert_eq!(marks.indices(SKILLS, &[], 0), vec![3]);
+        marks.clear();
+        assert!(!marks.any(SKILLS));
+    }
+
+    #[test]
+    fn test_range_with_filter() {
+        // The search matches docx, pptx and zip
+        let filtered = [0, 2, 4];
+        let mut marks = Marks::default();
+        marks.toggle_range(SKILLS, &filtered, 0);
+        assert!(marks.any(SKILLS));
+        assert_eq!(marks.indices(SKILLS, &filtered, 0), vec![0]);
+
+        // The range follows the selection, skipping skills the search hides
+        assert_eq!(marks.indices(SKILLS, &filtered, 2), vec![0, 2]);
+        marks.toggle_range(SKILLS, &filtered, 4);
+
+        // Once made, the range stays marked after the search is cleared
+        let all: Vec<usize> = (0..SKILLS.len()).collect();
+        assert_eq!(marks.indices(SKILLS, &all, 1), vec![0, 2, 4]);
+
+        // A range may run upwards from the anchor too
+        marks.clear();
+        marks.toggle_range(SKILLS, &all, 3);
+        assert_eq!(marks.indices(SKILLS, &all, 1), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_marks_survive_rescan() {
+        let mut marks = Marks::default();
+        marks.toggle("pdf");
+        marks.toggle("zip");
+        marks.toggle_range(SKILLS, &[], 3);
+
+        // A rescan reorders the skills and drops zip and xlsx
+        let rescanned = ["pdf", "csv", "docx"];
+        assert_eq!(marks.indices(&rescanned, &[0, 1, 2], 2), vec![0]);
+        assert!(marks.any(&rescanned));
+
+        // zip is marked again when it comes back
+        let restored = ["zip", "pdf"];
+        assert_eq!(marks.indices(&restored, &[0, 1], 0), vec![0, 1]);
+        assert!(!marks.any(&["csv"]));
+    }
+
+    #[test]
+    fn test_toggle_all() {
+        let filtered = [1, 3];
+        let mut marks = Marks::default();
+        marks.toggle("docx");
+        marks.toggle("pdf");
+        marks.toggle_all(SKILLS, &filtered);
+        assert_eq!(marks.indices(SKILLS, &[], 0), vec![0, 1, 3]);
+
+        // All matching skills were marked, so they are unmarked
+        marks.toggle_all(SKILLS, &filtered);
+        assert_eq!(marks.indices(SKILLS, &[], 0), vec![0]);
+    }
+
+    #[test]
+    fn test_report() {
+        let ok = |name: &str| (name.to_string(), Ok(()));
+        let err = |name: &str, e: &str| (name.to_string(), Err(e.to_string()));
+        let report = |results: &[(String, Result<(), String>)]| {
+            report("Linked", "to Claude Code", "Link", results)
+        };
+
+        assert_eq!(report(&[]), None);
+        assert_eq!(
+            report(&[ok("pdf")]),
+            Some(("Linked pdf to Claude Code".to_string(), StatusKind::Success))
+        );
+        assert_eq!(
+            report(&[err("pdf", "already linked")]),
+            Some(("Link failed: already linked".to_string(), StatusKind::Error))
+        );
+        assert_eq!(
+            report(&[ok("pdf"), ok("docx"), ok("zip")]),
+            Some((
+                "Linked 3 skills to Claude Code".to_string(),
+                StatusKind::Success
+            ))
+        );
+        assert_eq!(
+            report(&[ok("pdf"), err("docx", "already linked"), err("zip", "not found")]),
+            Some((
+                "Linked 1 of 3 skills to Claude Code; Link failed: docx (already linked), zip (not found)"
+                    .to_string(),
+                StatusKind::Error
+            ))
+        );
+    }
+}
@@ -0,0 +1,660 @@
+//! Claude Code plugin bundles: the manifest and the components besides skills
+//! (slash commands, subagents, hooks and MCP servers).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::error::PluginError;
+use super::metadata::split_frontmatter;
+use super::skill::{normalize, relative_path};
+use super::target::{LinkMode, LinkTarget, Naming};
+
+/// Path of the plugin manifest, relative to the plugin root.
+pub const PLUGIN_MANIFEST: &str = ".claude-plugin/plugin.json";
+
+/// The author of a plugin, as a name or with contact details.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Author {
+    Name(String),
+    Details {
+        name: String,
+        email: Option<String>,
+        url: Option<String>,
+    },
+}
+
+impl Author {
+    /// The author's name.
+    pub fn name(&self) -> &str {
+        match self {
+            Author::Name(name) | Author::Details { name, .. } => name,
+        }
+    }
+}
+
+/// One path or a list of paths, relative to the plugin root.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Paths {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Paths {
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        let paths: &[String] = match self {
+            Paths::One(path) => std::slice::from_ref(path),
+            Paths::Many(paths) => paths,
+        };
+        paths.iter().map(String::as_str)
+    }
+}
+
+/// Configuration given as a path to a JSON file or inline.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum PathOrInline {
+    Path(String),
+    Inline(Map<String, Value>),
+}
+
+/// The `.claude-plugin/plugin.json` of a plugin bundle.
+///
+/// Component paths add to the default `commands/`, `agents/`,
+/// `hooks/hooks.json` and `.mcp.json` locations.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<Author>,
+    pub homepage: Option<String>,
+    pub repository: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    pub commands: Option<Paths>,
+    pub agents: Option<Paths>,
+    pub hooks: Option<PathOrInline>,
+    pub mcp_servers: Option<PathOrInline>,
+}
+
+impl PluginManifest {
+    /// Read the manifest of the plugin at `root`, if it has one.
+    pub fn load(root: &Path) -> Option<Result<PluginManifest, String>> {
+        let content = fs::read_to_string(root.join(PLUGIN_MANIFEST)).ok()?;
+        Some(serde_json::from_str(&content).map_err(|e| format!("{}: {}", PLUGIN_MANIFEST, e)))
+    }
+}
+
+/// The kinds of components a plugin bundle can ship besides skills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    /// A slash command: a markdown prompt under `commands/`.
+    Command,
+    /// A subagent: a markdown definition under `agents/`.
+    Agent,
+    /// The handlers of one hook event, from `hooks/hooks.json`.
+    Hook,
+    /// An MCP server, from `.mcp.json`.
+    McpServer,
+}
+
+impl ComponentKind {
+    /// Get the stable identifier for this kind, as used in output.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ComponentKind::Command => "command",
+            ComponentKind::Agent => "agent",
+            ComponentKind::Hook => "hook",
+            ComponentKind::McpServer => "mcp-server",
+        }
+    }
+
+    /// Get the plural name of this kind, as shown in the TUI.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComponentKind::Command => "Commands",
+            ComponentKind::Agent => "Agents",
+            ComponentKind::Hook => "Hooks",
+            ComponentKind::McpServer => "MCP servers",
+        }
+    }
+
+    /// Get all component kinds.
+    pub fn all() -> &'static [ComponentKind] {
+        &[
+            ComponentKind::Command,
+            ComponentKind::Agent,
+            ComponentKind::Hook,
+            ComponentKind::McpServer,
+        ]
+    }
+
+    /// Check if components of this kind can be linked. Hooks and MCP servers
+    /// are configuration to merge into settings, not files to link.
+    pub fn is_linkable(&self) -> bool {
+        matches!(self, ComponentKind::Command | ComponentKind::Agent)
+    }
+
+    /// The directory components of this kind are linked into for a target.
+    ///
+    /// Only Claude Code targets, whose skills directory sits in a `.claude`
+    /// directory, take commands and agents: they go next to the skills, into
+    /// `.claude/commands` and `.claude/agents`. Components are only ever
+    /// symlinked, so targets that copy, like project targets, take none.
+    pub fn dir_for(&self, target: &LinkTarget) -> Option<PathBuf> {
+        let name = match self {
+            ComponentKind::Command => "commands",
+            ComponentKind::Agent => "agents",
+            ComponentKind::Hook | ComponentKind::McpServer => return None,
+        };
+        if target.mode == LinkMode::Copy {
+            return None;
+        }
+        let claude_dir = target
+            .dir
+            .parent()
+            .filter(|p| p.file_name().is_some_and(|n| n == ".claude"))?;
+        Some(claude_dir.join(name))
+    }
+}
+
+/// Frontmatter of a command or agent file; only what skir shows.
+#[derive(Debug, Default, Deserialize)]
+struct Frontmatter {
+    description: Option<String>,
+}
+
+/// A component of a plugin bundle other than a skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub kind: ComponentKind,
+    /// The command or agent name (`dir:name` for commands in subdirectories),
+    /// the hook event, or the MCP server name.
+    pub name: String,
+    /// The file defining the component.
+    pub path: PathBuf,
+    /// The description from frontmatter, or a summary of the configuration.
+    pub description: Option<String>,
+    /// The owner (username/org) of the parent plugin.
+    owner: String,
+    /// The repository name of the parent plugin.
+    repo: String,
+}
+
+impl Component {
+    /// Get the qualified name for this component (owner:repo:name).
+    pub fn qualified_name(&self) -> String {
+        format!("{}:{}:{}", self.owner, self.repo, self.name)
+    }
+
+    /// Get the link path for this component in a target, if it can be linked there.
+    pub fn link_path_for(&self, target: &LinkTarget) -> Option<PathBuf> {
+        let name = match target.naming {
+            Naming::Qualified => self.qualified_name(),
+            Naming::Plain => self.name.clone(),
+        };
+        Some(self.kind.dir_for(target)?.join(format!("{}.md", name)))
+    }
+
+    /// Check if this component is linked to a target. Broken symlinks count as not linked.
+    pub fn is_linked_to(&self, target: &LinkTarget) -> bool {
+        self.link_path_for(target).is_some_and(|p| {
+            p.is_symlink() && p.canonicalize().ok() == self.path.canonicalize().ok()
+        })
+    }
+
+    /// Check if this component is linked to any configured target.
+    pub fn is_linked(&self) -> bool {
+        LinkTarget::all().iter().any(|t| self.is_linked_to(t))
+    }
+
+    /// Symlink this component's file into a target.
+    pub fn link_to(&self, target: &LinkTarget) -> Result<(), PluginError> {
+        let link_path = self
+            .link_path_for(target)
+            .ok_or_else(|| PluginError::NotLinkable {
+                name: self.qualified_name(),
+                target: target.display_name().to_string(),
+            })?;
+        if link_path.symlink_metadata().is_ok() {
+            return Err(PluginError::AlreadyLinked {
+                name: self.qualified_name(),
+            });
+        }
+
+        let Some(parent) = link_path.parent() else {
+            return Err(PluginError::LinkFailed {
+                name: self.name.clone(),
+                reason: "invalid link path".to_string(),
+            });
+        };
+        fs::create_dir_all(parent)?;
+        let file = if target.relative {
+            relative_path(&parent.canonicalize()?, &self.path.canonicalize()?)
+        } else {
+            self.path.clone()
+        };
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&file, &link_path)?;
+
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(&file, &link_path)?;
+
+        Ok(())
+    }
+
+    /// Check if the link at `link_path` is a broken symlink to this component's file,
+    /// as left behind when an update removed or moved it.
+    fn is_own_link_broken(&self, link_path: &Path) -> bool {
+        let (Some(parent), Ok(dest)) = (link_path.parent(), fs::read_link(link_path)) else {
+            return false;
+        };
+        if link_path.exists() {
+            return false;
+        }
+        // Relative links were written against the resolved target directory
+        let parent = parent
+            .canonicalize()
+            .unwrap_or_else(|_| parent.to_path_buf());
+        let dest = normalize(&parent.join(dest));
+        let resolved = self
+            .path
+            .parent()
+            .and_then(|dir| dir.canonicalize().ok())
+            .zip(self.path.file_name())
+            .map(|(dir, name)| dir.join(name));
+        dest == self.path || resolved.is_some_and(|path| dest == path)
+    }
+
+    /// Remove this component's symlink from a target.
+    ///
+    /// Broken symlinks to this component's file are removed too, but a link to
+    /// another component of the same name is left alone.
+    pub fn unlink_from(&self, target: &LinkTarget) -> Result<(), PluginError> {
+        match self.link_path_for(target) {
+            Some(link_path) if self.is_linked_to(target) || self.is_own_link_broken(&link_path) => {
+                fs::remove_file(&link_path)?;
+                Ok(())
+            }
+            _ => Err(PluginError::NotLinked {
+                name: self.name.clone(),
+            }),
+        }
+    }
+}
+
+/// The manifest and components found in a plugin bundle.
+#[derive(Debug, Default)]
+pub(crate) struct Bundle {
+    pub manifest: Option<PluginManifest>,
+    pub components: Vec<Component>,
+    /// Files that could not be read or parsed, with the reason.
+    pub errors: Vec<String>,
+}
+
+impl Bundle {
+    /// Discover the components of the plugin bundle at `root`.
+    ///
+    /// Directories without a manifest are still searched in the default
+    /// locations. Broken files are reported in `errors` and skipped.
+    pub(crate) fn discover(root: &Path, owner: &str, repo: &str) -> Bundle {
+        let mut bundle = Bundle::default();
+        match PluginManifest::load(root) {
+            Some(Ok(manifest)) => bundle.manifest = Some(manifest),
+            Some(Err(e)) => bundle.errors.push(e),
+            None => {}
+        }
+        let manifest = bundle.manifest.clone().unwrap_or_default();
+
+        let mut add =
+            |kind: ComponentKind, name: String, path: &Path, description: Option<String>| {
+                // The same file may be both a default location and listed in the manifest
+                let duplicate = bundle
+                    .components
+                    .iter()
+                    .any(|c| c.kind == kind && c.name == name);
+                if !duplicate {
+                    bundle.components.push(Component {
+                        kind,
+                        name,
+                        path: path.to_path_buf(),
+                        description,
+                        owner: owner.to_string(),
+                        repo: repo.to_string(),
+                    });
+                }
+            };
+
+        for (kind, default, paths) in [
+            (ComponentKind::Command, "commands", &manifest.commands),
+            (ComponentKind::Agent, "agents", &manifest.agents),
+        ] {
+            let extra = paths.iter().flat_map(Paths::iter).map(|p| root.join(p));
+            for path in std::iter::once(root.join(default)).chain(extra) {
+                for (name, file) in markdown_files(&path) {
+                    let description = fs::read_to_string(&file)
+                        .ok()
+                        .and_then(|c| frontmatter_description(&c));
+                    add(kind, name, &file, description);
+                }
+            }
+        }
+
+        let mut errors = Vec::new();
+        let hooks = config_sources(
+            root,
+            "hooks/hooks.json",
+            manifest.hooks.as_ref(),
+            "hooks",
+            &mut errors,
+        );
+        for (file, config) in hooks {
+            let Some(Value::Object(events)) = config.get("hooks") else {
+                continue;
+            };
+            for (event, matchers) in events {
+                add(
+                    ComponentKind::Hook,
+                    event.clone(),
+                    &file,
+                    Some(summarize_hooks(matchers)),
+                );
+            }
+        }
+
+        let servers = config_sources(
+            root,
+            ".mcp.json",
+            manifest.mcp_servers.as_ref(),
+            "mcpServers",
+            &mut errors,
+        );
+        for (file, config) in servers {
+            // A file may list servers at the top level or under `mcpServers`
+            let servers = match config.get("mcpServers") {
+                Some(Value::Object(servers)) => servers,
+                _ => &config,
+            };
+            for (name, server) in servers {
+                add(
+                    ComponentKind::McpServer,
+                    name.clone(),
+                    &file,
+                    summarize_server(server),
+                );
+            }
+        }
+
+        bundle.errors.extend(errors);
+        bundle
+    }
+}
+
+/// Find the markdown files at `path`: the file itself, or those under the directory.
+///
+/// Files in subdirectories are named `dir:name`, as Claude Code namespaces them.
+fn markdown_files(path: &Path) -> Vec<(String, PathBuf)> {
+    fn walk(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        paths.sort();
+        for path in paths {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            if path.is_dir() {
+                walk(&path, &format!("{}{}:", prefix, name), files);
+            } else if let Some(stem) = name.strip_suffix(".md") {
+                files.push((format!("{}{}", prefix, stem), path));
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    if path.is_dir() {
+        walk(path, "", &mut files);
+    } else if let Some(stem) = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_suffix(".md"))
+    {
+        files.push((stem.to_string(), path.to_path_buf()));
+    }
+    files
+}
+
+/// The description from a markdown file's frontmatter, if any.
+fn frontmatter_description(content: &str) -> Option<String> {
+    let (yaml, _) = split_frontmatter(content)?.ok()?;
+    let frontmatter: Frontmatter = serde_norway::from_str(&yaml).ok()?;
+    frontmatter
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+}
+
+/// Collect the JSON configuration from the default file and from the manifest.
+///
+/// Inline configuration is wrapped under `key`, as if it had been read from a
+/// file, and attributed to the manifest.
+fn config_sources(
+    root: &Path,
+    default: &str,
+    configured: Option<&PathOrInline>,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Vec<(PathBuf, Map<String, Value>)> {
+    let mut files = vec![root.join(default)];
+    let mut sources = Vec::new();
+    match configured {
+        Some(PathOrInline::Path(path)) if root.join(path) != files[0] => {
+            files.push(root.join(path))
+        }
+        Some(PathOrInline::Path(_)) | None => {}
+        Some(PathOrInline::Inline(config)) => {
+            let mut wrapped = Map::new();
+            wrapped.insert(key.to_string(), Value::Object(config.clone()));
+            sources.push((root.join(PLUGIN_MANIFEST), wrapped));
+        }
+    }
+
+    for file in files {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        match serde_json::from_str(&content) {
+            Ok(config) => sources.push((file, config)),
+            Err(e) => {
+                let name = file.strip_prefix(root).unwrap_or(&file);
+                errors.push(format!("{}: {}", name.display(), e));
+            }
+        }
+    }
+    sources
+}
+
+/// Summarize the handlers of a hook event as `matcher: command` pairs.
+fn summarize_hooks(matchers: &Value) -> String {
+    let handlers: Vec<String> = matchers
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|m| {
+            let matcher = m
+                .get("matcher")
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .unwrap_or("*");
+            m.get("hooks")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(move |h| {
+                    let command = h
+                        .get("command")
+                        .or_else(|| h.get("prompt"))
+                        .and_then(Value::as_str)?;
+                    Some(format!("{}: {}", matcher, command))
+                })
+        })
+        .collect();
+    handlers.join("; ")
+}
+
+/// Summarize an MCP server as its command line or URL.
+fn summarize_server(server: &Value) -> Option<String> {
+    if let Some(url) = server.get("url").and_then(Value::as_str) {
+        return Some(url.to_string());
+    }
+    let command = server.get("command").and_then(Value::as_str)?;
+    let args = server
+        .get("args")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    Some(
+        std::iter::once(command)
+            .chain(args)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::testing::target;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_discover_bundle() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        fs::write(
+            root.join(PLUGIN_MANIFEST),
+            r#"{
+                "name": "tools",
+                "version": "1.2.0",
+                "author": {"name": "Me", "email": "me@example.com"},
+                "agents": ["./extra/reviewer.md"],
+                "mcpServers": {"db": {"command": "db-mcp", "args": ["--port", "5432"]}}
+            }"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("commands/git")).unwrap();
+        fs::write(
+            root.join("commands/deploy.md"),
+            "---\ndescription: Deploy the app\n---\nDeploy $ARGUMENTS",
+        )
+        .unwrap();
+        fs::write(
+            root.join("commands/git/commit.md"),
+            "Commit the staged changes",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("extra")).unwrap();
+        fs::write(
+            root.join("extra/reviewer.md"),
+            "---\nname: reviewer\ndescription: Reviews code\n---\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("hooks")).unwrap();
+        fs::write(
+            root.join("hooks/hooks.json"),
+            r#"{"hooks": {"PostToolUse": [{"matcher": "Write", "hooks": [{"type": "command", "command": "fmt.sh"}]}]}}"#,
+        )
+        .unwrap();
+        fs::write(root.join(".mcp.json"), "{ not json").unwrap();
+
+        let bundle = Bundle::discover(root, "me", "tools");
+        let manifest = bundle.manifest.unwrap();
+        assert_eq!(manifest.version.as_deref(), Some("1.2.0"));
+        assert_eq!(manifest.author.as_ref().map(Author::name), Some("Me"));
+
+        let components: Vec<(ComponentKind, &str, Option<&str>)> = bundle
+            .components
+            .iter()
+            .map(|c| (c.kind, c.name.as_str(), c.description.as_deref()))
+            .collect();
+        assert_eq!(
+            components,
+            vec![
+                (ComponentKind::Command, "deploy", Some("Deploy the app")),
+                (ComponentKind::Command, "git:commit", None),
+                (ComponentKind::Agent, "reviewer", Some("Reviews code")),
+                (ComponentKind::Hook, "PostToolUse", Some("Write: fmt.sh")),
+                (ComponentKind::McpServer, "db", Some("db-mcp --port 5432")),
+            ]
+        );
+        assert_eq!(bundle.errors.len(), 1);
+        assert!(bundle.errors[0].starts_with(".mcp.json: "));
+    }
+
+    #[test]
+    fn test_link_component() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("plugin/commands")).unwrap();
+        fs::write(dir.path().join("plugin/commands/deploy.md"), "Deploy").unwrap();
+        let bundle = Bundle::discover(&dir.path().join("plugin"), "me", "tools");
+        let command = &bundle.components[0];
+
+        let mut target = target(&dir.path().join(".claude").join("skills"));
+        let link_path = dir.path().join(".claude/commands/me:tools:deploy.md");
+        assert_eq!(command.link_path_for(&target), Some(link_path.clone()));
+
+        command.link_to(&target).unwrap();
+        assert!(command.is_linked_to(&target));
+        assert!(command.link_to(&target).is_err());
+        command.unlink_from(&target).unwrap();
+        assert!(!link_path.exists());
+
+        // With plain names, commands of two plugins share a link path
+        fs::create_dir_all(dir.path().join("other/commands")).unwrap();
+        fs::write(dir.path().join("other/commands/deploy.md"), "Deploy").unwrap();
+        let other = Bundle::discover(&dir.path().join("other"), "you", "tools");
+        let other = &other.components[0];
+        let plain = LinkTarget {
+            naming: Naming::Plain,
+            ..target.clone()
+        };
+        let plain_path = dir.path().join(".claude/commands/deploy.md");
+        command.link_to(&plain).unwrap();
+        assert!(!other.is_linked_to(&plain));
+        assert!(other.unlink_from(&plain).is_err());
+        assert!(plain_path.is_symlink());
+
+        // A broken link is removable only by the component it pointed to
+        fs::remove_file(dir.path().join("plugin/commands/deploy.md")).unwrap();
+        assert!(other.unlink_from(&plain).is_err());
+        command.unlink_from(&plain).unwrap();
+        assert!(!plain_path.is_symlink());
+
+        // Commands are not copied
+        let copy = LinkTarget {
+            mode: LinkMode::Copy,
+            ..target.clone()
+        };
+        assert!(command.link_path_for(&copy).is_none());
+        assert!(command.link_to(&copy).is_err());
+
+        // Targets of other tools take no commands
+        target.dir = dir.path().join(".codex").join("skills");
+        assert!(command.link_path_for(&target).is_none());
+        assert!(command.link_to(&target).is_err());
+    }
+}
@@ -0,0 +1,623 @@
+//! User configuration, read from `~/.config/skir/config.toml` and `SKIR_*`
+//! environment variables.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use super::error::PluginError;
+use super::manager::DEFAULT_CONCURRENCY;
+use super::source::parse_local_path;
+use super::target::{LinkMode, LinkTarget, Naming};
+
+/// Name of the configuration file.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Host used for `owner/repo` shorthand URLs unless configured otherwise.
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// A `[[targets]]` entry.
+///
+/// An entry named like a built-in target changes only the fields it sets;
+/// any other entry adds a target and must set `dir`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TargetConfig {
+    pub name: String,
+    pub display_name: Option<String>,
+    /// Absolute path, or relative to the home directory when starting with `~/`.
+    pub dir: Option<String>,
+    pub mode: Option<LinkMode>,
+    pub naming: Option<Naming>,
+    /// Write relative symlinks.
+    pub relative: Option<bool>,
+    /// Leave the target out, e.g. to hide a built-in target.
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// The `[ui]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct UiConfig {
+    /// Open the SKILL.md preview next to the skill list on start.
+    pub show_preview: Option<bool>,
+    /// Check plugins for upstream commits on start.
+    pub check_updates: Option<bool>,
+}
+
+/// The contents of the configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Where plugins and the lockfile are kept.
+    pub cache_dir: Option<String>,
+    /// Where skills adopted or replaced in link targets are kept.
+    pub data_dir: Option<String>,
+    /// Host for `owner/repo` shorthand URLs.
+    pub default_host: Option<String>,
+    /// How many plugins to update at once.
+    pub concurrency: Option<usize>,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub targets: Vec<TargetConfig>,
+}
+
+/// Read a path from an `XDG_*` variable, ignoring empty and relative values
+/// as the XDG base directory spec asks.
+fn env_dir(env: &Env, var: &str) -> Option<PathBuf> {
+    env(var).map(PathBuf::from).filter(|p| p.is_absolute())
+}
+
+impl Config {
+    /// Get the path of the configuration file.
+    ///
+    /// This is `$SKIR_CONFIG` if set, else `skir/config.toml` in
+    /// `$XDG_CONFIG_HOME` or `~/.config`.
+    pub fn path() -> Option<PathBuf> {
+        Self::path_in(&env_var)
+    }
+
+    fn path_in(env: &Env) -> Option<PathBuf> {
+        if let Some(path) = env("SKIR_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = env_dir(env, "XDG_CONFIG_HOME")
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))?;
+        Some(config_home.join("skir").join(CONFIG_FILE))
+    }
+
+    /// Load the configuration file, or the defaults if there is none.
+    pub fn load() -> Result<Config, PluginError> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// Load a configuration file from a specific path.
+    pub fn load_from(path: &Path) -> Result<Config, PluginError> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content, path)
+    }
+
+    /// Parse configuration content. `path` is only used for error messages.
+    pub fn parse(content: &str, path: &Path) -> Result<Config, PluginError> {
+        toml::from_str(content).map_err(|e| PluginError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason: e.message().to_string(),
+        })
+    }
+
+    /// Build the link targets: the built-in ones, plus those of `project` if
+    /// given, with the configured changes applied.
+    ///
+    /// `path` is only used for error messages.
+    pub fn link_targets(
+        &self,
+        path: &Path,
+        project: Option<&Path>,
+    ) -> Result<Vec<LinkTarget>, PluginError> {
+        let invalid = |reason: String| PluginError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let mut targets = LinkTarget::builtin();
+        if let Some(project) = project {
+            targets.extend(LinkTarget::project(project));
+        }
+        let mut disabled = Vec::new();
+        for entry in &self.targets {
+            if entry.name.is_empty() || entry.name.contains(['/', ':']) {
+                return Err(invalid(format!("invalid target name: {:?}", entry.name)));
+            }
+            if entry.disabled {
+                disabled.push(entry.name.as_str());
+                continue;
+            }
+
+            let dir = match &entry.dir {
+                Some(dir) => Some(
+                    parse_local_path(dir)
+                        .filter(|d| d.is_absolute())
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "target {}: dir must be absolute or start with ~/",
+                                entry.name
+                            ))
+                        })?,
+                ),
+                None => None,
+            };
+
+            let index = match targets.iter().position(|t| t.name == entry.name) {
+                Some(index) => index,
+                None => {
+                    let dir = dir
+                        .clone()
+                        .ok_or_else(|| invalid(format!("target {}: missing dir", entry.name)))?;
+                    targets.push(LinkTarget {
+                        name: entry.name.clone(),
+                        display_name: entry.name.clone(),
+                        dir,
+                        mode: LinkMode::default(),
+                        naming: Naming::default(),
+                        relative: false,
+                        project: None,
+                    });
+                    targets.len() - 1
+                }
+            };
+
+            let target = &mut targets[index];
+            if let Some(display_name) = &entry.display_name {
+                target.display_name = display_name.clone();
+            }
+            if let Some(dir) = dir {
+                target.dir = dir;
+            }
+            if let Some(mode) = entry.mode {
+                target.mode = mode;
+            }
+            if let Some(naming) = entry.naming {
+                target.naming = naming;
+            }
+            if let Some(relative) = entry.relative {
+                target.relative = relative;
+            }
+        }
+
+        targets.retain(|t| !disabled.contains(&t.name.as_str()));
+        Ok(targets)
+    }
+}
+
+/// Where a setting's value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    /// The configuration file.
+    File(PathBuf),
+    /// An environment variable.
+    Env(&'static str),
+    /// The project skir runs in.
+    Project(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${}", var),
+            Source::Project(root) => write!(f, "project {}", root.display()),
+        }
+    }
+}
+
+/// A setting's effective value and where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T, source: Source) -> Self {
+        Self { value, source }
+    }
+}
+
+/// Looks up environment variables; swapped out in tests.
+type Env = dyn Fn(&str) -> Option<String>;
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// The effective configuration: defaults, overridden by the configuration
+/// file, overridden by `SKIR_*` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The configuration file, whether or not it exists.
+    pub config_path: Option<PathBuf>,
+    pub cache_dir: Setting<PathBuf>,
+    pub data_dir: Setting<PathBuf>,
+    pub default_host: Setting<String>,
+    pub concurrency: Setting<usize>,
+    pub show_preview: Setting<bool>,
+    pub check_updates: Setting<bool>,
+    pub targets: Vec<LinkTarget>,
+    /// Where each of `targets` was defined, in the same order.
+    pub target_sources: Vec<Source>,
+}
+
+impl Settings {
+    /// Load the configuration file and environment, with the link targets of
+    /// `project` if given.
+    pub fn load(project: Option<&Path>) -> Result<Settings, PluginError> {
+        let path = Config::path();
+        let config = match &path {
+            Some(path) if path.exists() => Config::load_from(path)?,
+            _ => Config::default(),
+        };
+        Self::resolve(&config, path, &env_var, project)
+    }
+
+    /// Merge `config`, read from `path`, with the defaults and environment.
+    fn resolve(
+        config: &Config,
+        path: Option<PathBuf>,
+        env: &Env,
+        project: Option<&Path>,
+    ) -> Result<Settings, PluginError> {
+        let file = Source::File(path.clone().unwrap_or_default());
+        let invalid = |source: &Source, reason: String| PluginError::InvalidConfig {
+            path: match source {
+                Source::Env(var) => PathBuf::from(format!("${}", var)),
+                _ => path.clone().unwrap_or_default(),
+            },
+            reason,
+        };
+
+        // $SKIR_*, then the file, then $XDG_*_HOME/skir, then ~/<default>/skir
+        let dir = |var: &'static str,
+                   key: &str,
+                   value: &Option<String>,
+                   xdg: &'static str,
+                   default: &str|
+         -> Result<Setting<PathBuf>, PluginError> {
+            if let Some(dir) = env(var).map(PathBuf::from) {
+                if !dir.is_absolute() {
+                    return Err(invalid(&Source::Env(var), "must be absolute".to_string()));
+                }
+                Ok(Setting::new(dir, Source::Env(var)))
+            } else if let Some(dir) = value {
+                let dir = parse_local_path(dir)
+                    .filter(|d| d.is_absolute())
+                    .ok_or_else(|| {
+                        invalid(&file, format!("{} must be absolute or start with ~/", key))
+                    })?;
+                Ok(Setting::new(dir, file.clone()))
+            } else if let Some(home) = env_dir(env, xdg) {
+                Ok(Setting::new(home.join("skir"), Source::Env(xdg)))
+            } else {
+                let home = dirs::home_dir().ok_or(PluginError::CacheDirectoryNotFound)?;
+                Ok(Setting::new(
+                    home.join(default).join("skir"),
+                    Source::Default,
+                ))
+            }
+        };
+        let cache_dir = dir(
+            "SKIR_CACHE_DIR",
+            "cache-dir",
+            &config.cache_dir,
+            "XDG_CACHE_HOME",
+            ".cache",
+        )?;
+        let data_dir = dir(
+            "SKIR_DATA_DIR",
+            "data-dir",
+            &config.data_dir,
+            "XDG_DATA_HOME",
+            ".local/share",
+        )?;
+
+        let default_host = match (env("SKIR_DEFAULT_HOST"), &config.default_host) {
+            (Some(host), _) => Setting::new(host, Source::Env("SKIR_DEFAULT_HOST")),
+            (None, Some(host)) => Setting::new(host.clone(), file.clone()),
+            (None, None) => Setting::new(DEFAULT_HOST.to_string(), Source::Default),
+        };
+        if default_host.value.is_empty() || default_host.value.contains(['/', ':', ' ']) {
+            let reason = format!("invalid default host: {:?}", default_host.value);
+            return Err(invalid(&default_host.source, reason));
+        }
+
+        let concurrency = match (env("SKIR_CONCURRENCY"), config.concurrency) {
+            (Some(jobs), _) => {
+                let source = Source::Env("SKIR_CONCURRENCY");
+                let jobs = jobs
+                    .parse()
+                    .map_err(|_| invalid(&source, format!("invalid concurrency: {:?}", jobs)))?;
+                Setting::new(jobs, source)
+            }
+            (None, Some(jobs)) => Setting::new(jobs, file.clone()),
+            (None, None) => Setting::new(DEFAULT_CONCURRENCY, Source::Default),
+        };
+        if concurrency.value == 0 {
+            return Err(invalid(
+                &concurrency.source,
+                "concurrency must be at least 1".to_string(),
+            ));
+        }
+
+        let ui = |value: Option<bool>, default: bool| match value {
+            Some(value) => Setting::new(value, file.clone()),
+            None => Setting::new(default, Source::Default),
+        };
+
+        let targets =
+            config.link_targets(path.as_deref().unwrap_or(Path::new(CONFIG_FILE)), project)?;
+        let target_sources = targets
+            .iter()
+            .map(|t| match &t.project {
+                _ if config.targets.iter().any(|e| e.name == t.name) => file.clone(),
+                Some(root) => Source::Project(root.clone()),
+                None => Source::Default,
+            })
+            .collect();
+
+        Ok(Settings {
+            show_preview: ui(config.ui.show_preview, false),
+            check_updates: ui(config.ui.check_updates, true),
+            targets,
+            target_sources,
+            config_path: path,
+            cache_dir,
+            data_dir,
+            default_host,
+            concurrency,
+        })
+    }
+
+    /// Set the settings returned by [`Settings::get`].
+    ///
+    /// Only the first call has an effect; settings cannot change while skir runs.
+    pub fn init(settings: Settings) {
+        let _ = SETTINGS.set(settings);
+    }
+
+    /// Get the settings, loading them without a project if they were not set.
+    pub fn get() -> Result<&'static Settings, PluginError> {
+        if let Some(settings) = SETTINGS.get() {
+            return Ok(settings);
+        }
+        let settings = Self::load(None)?;
+        Ok(SETTINGS.get_or_init(|| settings))
+    }
+
+    /// The directory plugins are cloned into.
+    pub fn repos_dir(&self) -> PathBuf {
+        self.cache_dir.value.join("repos")
+    }
+
+    /// The directory marketplaces are cloned into.
+    pub fn marketplaces_dir(&self) -> PathBuf {
+        self.cache_dir.value.join("marketplaces")
+    }
+
+    /// The directory unmanaged skills are moved into when adopted.
+    pub fn adopted_dir(&self) -> PathBuf {
+        self.data_dir.value.join("adopted")
+    }
+
+    /// The directory unmanaged clones are set aside in when replaced by links.
+    pub fn replaced_dir(&self) -> PathBuf {
+        self.data_dir.value.join("replaced")
+    }
+}
+
+/// The effective settings, set once at startup.
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(content: &str) -> Result<Vec<LinkTarget>, PluginError> {
+        let path = Path::new("config.toml");
+        Config::parse(content, path)?.link_targets(path, None)
+    }
+
+    #[test]
+    fn test_builtin_targets() {
+        let targets = targets("").unwrap();
+        let names: Vec<&str> = targets.iter().map(|t| t.id()).collect();
+        assert_eq!(names, vec!["claude", "codex"]);
+        assert!(targets[0].dir.ends_with(".claude/skills"));
+    }
+
+    #[test]
+    fn test_configured_targets() {
+        let content = r#"
+[[targets]]
+name = "claude"
+mode = "copy"
+
+[[targets]]
+name = "codex"
+disabled = true
+
+[[targets]]
+name = "team"
+display-name = "Team share"
+dir = "/srv/skills"
+naming = "plain"
+"#;
+        let targets = targets(content).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].id(), "claude");
+        assert_eq!(targets[0].mode, LinkMode::Copy);
+        assert_eq!(targets[0].display_name(), "Claude Code");
+        assert_eq!(targets[1].display_name(), "Team share");
+        assert_eq!(targets[1].dir, PathBuf::from("/srv/skills"));
+        assert_eq!(targets[1].naming, Naming::Plain);
+        assert_eq!(targets[1].mode, LinkMode::Symlink);
+    }
+
+    #[test]
+    fn test_project_targets() {
+        let content = "[[targets]]\nname = \"project-claude\"\nmode = \"symlink\"\n";
+        let path = Path::new("config.toml");
+        let project = Path::new("/work/app");
+        let targets = Config::parse(content, path)
+            .unwrap()
+            .link_targets(path, Some(project))
+            .unwrap();
+
+        let names: Vec<&str> = targets.iter().map(|t| t.id()).collect();
+        assert_eq!(
+            names,
+            vec!["claude", "codex", "project-claude", "project-codex"]
+        );
+        assert_eq!(targets[2].dir, PathBuf::from("/work/app/.claude/skills"));
+        assert_eq!(targets[2].mode, LinkMode::Symlink);
+        assert_eq!(targets[3].mode, LinkMode::Copy);
+        assert_eq!(targets[3].project.as_deref(), Some(project));
+    }
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_config_path() {
+        let path = Config::path_in(&env(&[("XDG_CONFIG_HOME", "/xdg")])).unwrap();
+        assert_eq!(path, PathBuf::from("/xdg/skir/config.toml"));
+        let path = Config::path_in(&env(&[("XDG_CONFIG_HOME", "xdg")])).unwrap();
+        assert!(path.ends_with(".config/skir/config.toml"));
+        let path = Config::path_in(&env(&[
+            ("SKIR_CONFIG", "/etc/skir.toml"),
+            ("XDG_CONFIG_HOME", "/xdg"),
+        ]))
+        .unwrap();
+        assert_eq!(path, PathBuf::from("/etc/skir.toml"));
+    }
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings = Settings::resolve(&Config::default(), None, &env(&[]), None).unwrap();
+        assert!(settings.cache_dir.value.ends_with(".cache/skir"));
+        assert_eq!(settings.cache_dir.source, Source::Default);
+        assert_eq!(settings.default_host.value, DEFAULT_HOST);
+        assert_eq!(settings.concurrency.value, DEFAULT_CONCURRENCY);
+        assert!(!settings.show_preview.value);
+        assert!(settings.check_updates.value);
+        assert_eq!(
+            settings.target_sources,
+            vec![Source::Default, Source::Default]
+        );
+
+        let settings = Settings::resolve(
+            &Config::default(),
+            None,
+            &env(&[("XDG_CACHE_HOME", "/xdg")]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(settings.cache_dir.value, PathBuf::from("/xdg/skir"));
+        assert_eq!(settings.cache_dir.source, Source::Env("XDG_CACHE_HOME"));
+        assert!(settings.data_dir.value.ends_with(".local/share/skir"));
+    }
+
+    #[test]
+    fn test_settings_precedence() {
+        let content = r#"
+cache-dir = "/srv/cache"
+default-host = "git.example.com"
+concurrency = 8
+
+[ui]
+show-preview = true
+
+[[targets]]
+name = "codex"
+mode = "copy"
+"#;
+        let path = PathBuf::from("/etc/skir/config.toml");
+        let config = Config::parse(content, &path).unwrap();
+        let file = Source::File(path.clone());
+
+        let vars = env(&[("XDG_CACHE_HOME", "/xdg"), ("SKIR_CONCURRENCY", "2")]);
+        let settings = Settings::resolve(
+            &config,
+            Some(path.clone()),
+            &vars,
+            Some(Path::new("/work/app")),
+        )
+        .unwrap();
+        assert_eq!(
+            settings.cache_dir,
+            Setting::new(PathBuf::from("/srv/cache"), file.clone())
+        );
+        assert_eq!(
+            settings.default_host,
+            Setting::new("git.example.com".to_string(), file.clone())
+        );
+        assert_eq!(
+            settings.concurrency,
+            Setting::new(2, Source::Env("SKIR_CONCURRENCY"))
+        );
+        assert_eq!(settings.show_preview, Setting::new(true, file.clone()));
+        assert_eq!(settings.check_updates.source, Source::Default);
+        assert_eq!(settings.repos_dir(), PathBuf::from("/srv/cache/repos"));
+        assert_eq!(
+            settings.target_sources,
+            vec![
+                Source::Default,
+                file,
+                Source::Project(PathBuf::from("/work/app")),
+                Source::Project(PathBuf::from("/work/app")),
+            ]
+        );
+
+        let vars = env(&[
+            ("SKIR_CACHE_DIR", "/tmp/skir"),
+            ("SKIR_DEFAULT_HOST", "gitlab.com"),
+        ]);
+        let settings = Settings::resolve(&config, Some(path.clone()), &vars, None).unwrap();
+        assert_eq!(settings.cache_dir.value, PathBuf::from("/tmp/skir"));
+        assert_eq!(
+            settings.default_host.source,
+            Source::Env("SKIR_DEFAULT_HOST")
+        );
+
+        for vars in [
+            &[("SKIR_CONCURRENCY", "many")],
+            &[("SKIR_CONCURRENCY", "0")],
+            &[("SKIR_DEFAULT_HOST", "a/b")],
+            &[("SKIR_CACHE_DIR", "cache")],
+            &[("SKIR_DATA_DIR", "./data")],
+        ] {
+            assert!(Settings::resolve(&config, Some(path.clone()), &env(vars), None).is_err());
+        }
+    }
+
+    #[test]
+    fn test_invalid_targets() {
+        assert!(targets("[[targets]]\nname = \"cursor\"\n").is_err());
+        assert!(targets("[[targets]]\nname = \"cursor\"\ndir = \"skills\"\n").is_err());
+        assert!(targets("[[targets]]\nname = \"a:b\"\ndir = \"/tmp\"\n").is_err());
+        assert!(targets("[[targets]]\nname = \"x\"\ndir = \"/tmp\"\ncolour = \"red\"\n").is_err());
+    }
+}
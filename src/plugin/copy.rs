@@ -0,0 +1,246 @@
+//! Copy-mode links: skill directories copied into a target instead of symlinked.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::error::PluginError;
+
+/// File inside a copied skill that records where it came from.
+pub(crate) const RECORD_FILE: &str = ".skir-copy.toml";
+
+/// Which skill a copy was made from, and the hash of its content when copied.
+///
+/// The skill is recorded by qualified name rather than by path, so a copy
+/// committed to a project resolves against the plugin cache of whoever
+/// checks it out, and does not leak the path of the cache it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CopyRecord {
+    /// Qualified name of the copied skill, `owner:repo:skill`.
+    pub skill: String,
+    /// SHA-256 of the copied files, as computed by [`hash_dir`].
+    pub hash: String,
+}
+
+impl CopyRecord {
+    /// Read the record of a copied skill, if `dir` is one.
+    pub(crate) fn read(dir: &Path) -> Option<CopyRecord> {
+        let content = fs::read_to_string(dir.join(RECORD_FILE)).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    fn write(&self, dir: &Path) -> Result<(), PluginError> {
+        let content = toml::to_string(self).map_err(|e| PluginError::LinkFailed {
+            name: dir.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        fs::write(dir.join(RECORD_FILE), content)?;
+        Ok(())
+    }
+}
+
+/// List the files under `root`, relative to it and sorted.
+///
+/// VCS directories and copy records are left out.
+pub(crate) fn list_files(root: &Path) -> Result<Vec<PathBuf>, PluginError> {
+    fn walk(root: &Path, current: &Path, files: &mut Vec<PathBuf>) -> Result<(), PluginError> {
+        for entry in fs::read_dir(current)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if path.is_dir() {
+                if name != ".git" && name != ".svn" && name != ".hg" {
+                    walk(root, &path, files)?;
+                }
+            } else if name != RECORD_FILE
+                && let Ok(relative) = path.strip_prefix(root)
+            {
+                files.push(relative.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(root, root, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Hash the files of a skill directory.
+///
+/// Both file names and contents are hashed, so renames count as changes.
+pub(crate) fn hash_dir(dir: &Path) -> Result<String, PluginError> {
+    let mut hasher = Sha256::new();
+    for file in list_files(dir)? {
+        let content = fs::read(dir.join(&file))?;
+        hasher.update(file.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// The size and modification time of each file under a directory.
+type Fingerprint = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+/// Hashes from [`hash_dir_cached`], keyed by directory.
+static HASHES: OnceLock<Mutex<HashMap<PathBuf, (Fingerprint, String)>>> = OnceLock::new();
+
+/// Hash a skill directory like [`hash_dir`], reusing the last hash while no
+/// file was added, removed or modified since.
+///
+/// Only file metadata is read when nothing changed, so this is cheap to call
+/// on every frame.
+pub(crate) fn hash_dir_cached(dir: &Path) -> Result<String, PluginError> {
+    let mut fingerprint = Fingerprint::new();
+    for file in list_files(dir)? {
+        let metadata = fs::metadata(dir.join(&file))?;
+        fingerprint.push((file, metadata.len(), metadata.modified().ok()));
+    }
+
+    let hashes = HASHES.get_or_init(Default::default);
+    if let Some((cached, hash)) = hashes.lock().unwrap_or_else(|e| e.into_inner()).get(dir)
+        && *cached == fingerprint
+    {
+        return Ok(hash.clone());
+    }
+    let hash = hash_dir(dir)?;
+    hashes
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(dir.to_path_buf(), (fingerprint, hash.clone()));
+    Ok(hash)
+}
+
+/// Copy the directory `source` of the skill named `skill` (qualified) to
+/// `dest`, replacing an earlier copy.
+///
+/// The copy is assembled next to `dest` and moved into place, so a failed
+/// copy never leaves a half-written skill behind.
+pub(crate) fn copy_dir(source: &Path, dest: &Path, skill: &str) -> Result<(), PluginError> {
+    let name = dest.file_name().and_then(|n| n.to_str()).unwrap_or("skill");
+    let staging = dest.with_file_name(format!(".{}.skir-tmp", name));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+
+    let result = (|| {
+        for file in list_files(source)? {
+            let to = staging.join(&file);
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(source.join(&file), to)?;
+        }
+        fs::create_dir_all(&staging)?;
+        let record = CopyRecord {
+            skill: skill.to_string(),
+            hash: hash_dir(&staging)?,
+        };
+        record.write(&staging)
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    if dest.symlink_metadata().is_ok() {
+        remove_copy(dest)?;
+    }
+    fs::rename(&staging, dest)?;
+    Ok(())
+}
+
+/// Remove a copied skill. Refuses to touch directories skir did not create.
+pub(crate) fn remove_copy(dest: &Path) -> Result<(), PluginError> {
+    if CopyRecord::read(dest).is_none() {
+        return Err(PluginError::LinkFailed {
+            name: dest.display().to_string(),
+            reason: "directory was not created by skir".to_string(),
+        });
+    }
+    fs::remove_dir_all(dest)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_and_hash() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("pdf");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::create_dir_all(source.join(".git")).unwrap();
+        fs::write(source.join("SKILL.md"), "# PDF").unwrap();
+        fs::write(source.join("scripts/run.py"), "print()").unwrap();
+        fs::write(source.join(".git/HEAD"), "ref").unwrap();
+
+        let dest = dir.path().join("target").join("pdf");
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        copy_dir(&source, &dest, "me:tools:pdf").unwrap();
+
+        assert!(dest.join("scripts/run.py").is_file());
+        assert!(!dest.join(".git").exists());
+        let record = CopyRecord::read(&dest).unwrap();
+        assert_eq!(record.skill, "me:tools:pdf");
+        // Nothing ties the copy to this machine's paths
+        let content = fs::read_to_string(dest.join(RECORD_FILE)).unwrap();
+        assert!(!content.contains(&*dir.path().to_string_lossy()));
+        assert_eq!(record.hash, hash_dir(&source).unwrap());
+        assert_eq!(record.hash, hash_dir(&dest).unwrap());
+
+        // Editing either side changes the hash
+        fs::write(dest.join("SKILL.md"), "# Edited").unwrap();
+        assert_ne!(hash_dir(&dest).unwrap(), record.hash);
+
+        // Copying again replaces the old copy
+        copy_dir(&source, &dest, "me:tools:pdf").unwrap();
+        assert_eq!(fs::read_to_string(dest.join("SKILL.md")).unwrap(), "# PDF");
+
+        remove_copy(&dest).unwrap();
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_hash_dir_cached() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "# PDF").unwrap();
+        assert_eq!(
+            hash_dir_cached(dir.path()).unwrap(),
+            hash_dir(dir.path()).unwrap()
+        );
+
+        // A change in size is picked up even within the mtime resolution
+        fs::write(dir.path().join("SKILL.md"), "# Edited").unwrap();
+        assert_eq!(
+            hash_dir_cached(dir.path()).unwrap(),
+            hash_dir(dir.path()).unwrap()
+        );
+        fs::write(dir.path().join("notes.md"), "").unwrap();
+        assert_eq!(
+            hash_dir_cached(dir.path()).unwrap(),
+            hash_dir(dir.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_remove_copy_refuses_unmanaged() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "# Mine").unwrap();
+        assert!(remove_copy(dir.path()).is_err());
+        assert!(dir.path().join("SKILL.md").exists());
+    }
+}
@@ -0,0 +1,404 @@
+//! Health checks for the entries of link targets' skills directories.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::copy::{self, CopyRecord};
+use super::error::PluginError;
+use super::git::git_config_get;
+use super::plugin::Plugin;
+use super::skill::Skill;
+use super::source::GitSource;
+use super::target::LinkTarget;
+
+/// The health of an entry in a target's skills directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    /// A link skir made, for an installed skill, under the expected name.
+    Healthy,
+    /// A symlink that does not resolve.
+    Broken,
+    /// A symlink to a directory that is not an installed skill.
+    OutsideCache,
+    /// A copy of a skill that is not installed, e.g. one committed to a
+    /// project by someone else.
+    NotInstalled,
+    /// A directory or file skir did not create.
+    Unmanaged,
+    /// A second link to a skill, under a name other than the expected one.
+    Duplicate,
+}
+
+impl Health {
+    /// Get the stable identifier for this state, as used in output.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Health::Healthy => "healthy",
+            Health::Broken => "broken",
+            Health::OutsideCache => "outside-cache",
+            Health::NotInstalled => "not-installed",
+            Health::Unmanaged => "unmanaged",
+            Health::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// A way to fix an unhealthy entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// Replace the entry with a fresh link to its skill, under the expected name.
+    Relink,
+    /// Remove the symlink or copy.
+    Remove,
+    /// Register the linked directory as a local plugin and link its skill instead.
+    Adopt,
+    /// Swap an unmanaged directory for a link to the skill or repository it
+    /// came from (see [`Origin`]).
+    Replace,
+}
+
+impl Repair {
+    /// Get the stable identifier for this repair, as used on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Repair::Relink => "relink",
+            Repair::Remove => "remove",
+            Repair::Adopt => "adopt",
+            Repair::Replace => "replace",
+        }
+    }
+}
+
+/// Where an unmanaged directory came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The same files as this installed skill, by qualified name.
+    Skill(String),
+    /// A clone of this repository, by its origin URL.
+    Remote(String),
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::Skill(name) => write!(f, "same as {}", name),
+            Origin::Remote(url) => write!(f, "clone of {}", url),
+        }
+    }
+}
+
+/// An entry in a target's skills directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkEntry {
+    pub target: LinkTarget,
+    pub path: PathBuf,
+    pub health: Health,
+    /// Qualified name of the installed skill the entry belongs to: the one it
+    /// links to, or else the one whose link would have its name.
+    pub skill: Option<String>,
+    /// Where a symlink points, or the installed skill a copy was made from.
+    pub points_to: Option<PathBuf>,
+    /// Whether skir created the entry (a symlink or a recorded copy).
+    pub managed: bool,
+    /// Where an unmanaged directory came from, if it could be told.
+    pub origin: Option<Origin>,
+}
+
+impl LinkEntry {
+    /// The entry's file name.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// The repairs that apply to this entry, the recommended one first.
+    ///
+    /// Unmanaged directories are never removed, only adopted or replaced.
+    /// See [`LinkEntry::fix`] for the one applied without asking.
+    pub fn repairs(&self) -> Vec<Repair> {
+        match self.health {
+            Health::Healthy => vec![],
+            Health::Broken if self.skill.is_some() => vec![Repair::Relink, Repair::Remove],
+            Health::Broken => vec![Repair::Remove],
+            Health::OutsideCache if self.skill.is_some() => {
+                vec![Repair::Adopt, Repair::Relink, Repair::Remove]
+            }
+            Health::OutsideCache => vec![Repair::Adopt, Repair::Remove],
+            Health::Unmanaged if self.origin.is_some() => vec![Repair::Replace, Repair::Adopt],
+            Health::Unmanaged if self.path.is_dir() => vec![Repair::Adopt],
+            Health::Unmanaged | Health::NotInstalled => vec![],
+            Health::Duplicate => vec![Repair::Remove, Repair::Relink],
+        }
+    }
+
+    /// The repair to apply without asking: broken links are relinked, or
+    /// removed when their skill is gone.
+    ///
+    /// Other entries may be skills written by hand, so they are only
+    /// repaired when a repair is chosen explicitly.
+    pub fn fix(&self) -> Option<Repair> {
+        match self.health {
+            Health::Broken => self.repairs().first().copied(),
+            _ => None,
+        }
+    }
+
+    /// Whether the entry is a problem: unmanaged entries may be skills
+    /// written by hand, and copies of skills that are not installed still
+    /// work, so both are only reported.
+    pub fn is_problem(&self) -> bool {
+        !matches!(
+            self.health,
+            Health::Healthy | Health::Unmanaged | Health::NotInstalled
+        )
+    }
+
+    /// The directory this entry's content lives in, to register when adopting.
+    pub(crate) fn adoptable_dir(&self) -> Option<&Path> {
+        match self.health {
+            Health::OutsideCache => self.points_to.as_deref().filter(|p| p.is_dir()),
+            Health::Unmanaged => Some(self.path.as_path()).filter(|p| p.is_dir()),
+            _ => None,
+        }
+    }
+
+    /// Remove a symlink or a recorded copy. Unmanaged entries are left alone.
+    pub(crate) fn remove(&self) -> Result<(), PluginError> {
+        if self.path.is_symlink() {
+            fs::remove_file(&self.path)?;
+            Ok(())
+        } else {
+            copy::remove_copy(&self.path)
+        }
+    }
+}
+
+/// Find out where an unmanaged directory came from.
+///
+/// A directory with its own `.git` is matched by its origin remote; any
+/// other by comparing its files with those of the installed skills, whose
+/// hashes are computed once into `hashes`.
+fn identify(
+    dir: &Path,
+    skills: &[&Skill],
+    hashes: &mut Option<HashMap<String, String>>,
+) -> Option<Origin> {
+    if dir.join(".git").exists() {
+        // The URL as configured, before any insteadOf rewriting
+        let url = git_config_get(dir, "remote.origin.url")?;
+        return GitSource::parse(&url)
+            .is_ok()
+            .then_some(Origin::Remote(url));
+    }
+
+    let hash = copy::hash_dir(dir).ok()?;
+    let hashes = hashes.get_or_insert_with(|| {
+        skills
+            .iter()
+            .filter_map(|s| Some((copy::hash_dir(s.dir()).ok()?, s.qualified_name())))
+            .collect()
+    });
+    hashes.get(&hash).cloned().map(Origin::Skill)
+}
+
+/// Classify every entry in the skills directories of `targets`.
+///
+/// Hidden entries, such as copies being staged, are skipped.
+pub fn diagnose(
+    plugins: &[Arc<Plugin>],
+    targets: &[LinkTarget],
+) -> Result<Vec<LinkEntry>, PluginError> {
+    let skills: Vec<&Skill> = plugins.iter().flat_map(|p| p.skills()).collect();
+    let by_dir: HashMap<PathBuf, &Skill> = skills
+        .iter()
+        .filter_map(|s| Some((s.dir().canonicalize().ok()?, *s)))
+        .collect();
+    let by_name: HashMap<String, &Skill> =
+        skills.iter().map(|s| (s.qualified_name(), *s)).collect();
+
+    let mut hashes = None;
+    let mut entries = Vec::new();
+    for target in targets {
+        let Ok(dir_entries) = fs::read_dir(&target.dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = dir_entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                !p.file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        paths.sort();
+
+        let by_link_path: HashMap<PathBuf, &Skill> = skills
+            .iter()
+            .map(|s| (s.link_path_for(target), *s))
+            .collect();
+
+        for path in paths {
+            let expected = by_link_path.get(&path).map(|s| s.qualified_name());
+            let record = CopyRecord::read(&path);
+            // The installed skill a copy was made from, resolved by name
+            let copied = record.as_ref().and_then(|r| by_name.get(&r.skill).copied());
+            let (points_to, managed) = if path.is_symlink() {
+                (fs::read_link(&path).ok(), true)
+            } else {
+                (copied.map(|s| s.dir().to_path_buf()), record.is_some())
+            };
+
+            // Where the entry's content comes from
+            let source = if path.is_symlink() {
+                path.canonicalize().ok()
+            } else {
+                copied.and_then(|s| s.dir().canonicalize().ok())
+            };
+
+            let (health, skill) = if !managed {
+                (Health::Unmanaged, expected)
+            } else if let Some(record) = record.filter(|_| copied.is_none()) {
+                (Health::NotInstalled, Some(record.skill))
+            } else if let Some(source) = source {
+                match by_dir.get(&source) {
+                    Some(skill) if skill.link_path_for(target) == path => {
+                        (Health::Healthy, Some(skill.qualified_name()))
+                    }
+                    Some(skill) => (Health::Duplicate, Some(skill.qualified_name())),
+                    None => (Health::OutsideCache, expected),
+                }
+            } else {
+                (Health::Broken, expected)
+            };
+
+            let origin = match health {
+                Health::Unmanaged if path.is_dir() => identify(&path, &skills, &mut hashes),
+                _ => None,
+            };
+
+            entries.push(LinkEntry {
+                target: target.clone(),
+                path,
+                health,
+                skill,
+                points_to,
+                managed,
+                origin,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::testing::target;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diagnose() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("me").join("tools");
+        for name in ["pdf", "xlsx"] {
+            fs::create_dir_all(repo.join(name)).unwrap();
+            fs::write(repo.join(name).join("SKILL.md"), format!("# {}", name)).unwrap();
+        }
+        let elsewhere = dir.path().join("elsewhere");
+        fs::create_dir_all(&elsewhere).unwrap();
+
+        let plugin = Plugin::build(
+            "github.com".to_string(),
+            "me".to_string(),
+            "tools".to_string(),
+            repo.clone(),
+        )
+        .unwrap();
+        let plugins = vec![Arc::new(plugin)];
+        let target = target(&dir.path().join("skills"));
+        let skill = plugins[0]
+            .skills()
+            .iter()
+            .find(|s| s.name == "pdf")
+            .unwrap();
+        skill.link_to(&target).unwrap();
+
+        let skills = &target.dir;
+        let symlink =
+            |from: &Path, name: &str| std::os::unix::fs::symlink(from, skills.join(name)).unwrap();
+        symlink(&repo.join("pdf"), "pdf");
+        symlink(&repo.join("gone"), "me:tools:xlsx");
+        symlink(&elsewhere, "mine");
+        fs::create_dir_all(skills.join("by-hand")).unwrap();
+        fs::create_dir_all(skills.join(".pdf.skir-tmp")).unwrap();
+
+        let entries = diagnose(&plugins, std::slice::from_ref(&target)).unwrap();
+        let health: Vec<(String, Health, Option<String>)> = entries
+            .iter()
+            .map(|e| (e.name(), e.health, e.skill.clone()))
+            .collect();
+        assert_eq!(
+            health,
+            vec![
+                ("by-hand".to_string(), Health::Unmanaged, None),
+                (
+                    "me:tools:pdf".to_string(),
+                    Health::Healthy,
+                    Some("me:tools:pdf".to_string())
+                ),
+                (
+                    "me:tools:xlsx".to_string(),
+                    Health::Broken,
+                    Some("me:tools:xlsx".to_string())
+                ),
+                ("mine".to_string(), Health::OutsideCache, None),
+                (
+                    "pdf".to_string(),
+                    Health::Duplicate,
+                    Some("me:tools:pdf".to_string())
+                ),
+            ]
+        );
+        assert_eq!(entries[0].repairs(), vec![Repair::Adopt]);
+        assert_eq!(entries[2].repairs(), vec![Repair::Relink, Repair::Remove]);
+        assert_eq!(entries[3].repairs(), vec![Repair::Adopt, Repair::Remove]);
+        assert_eq!(entries[0].origin, None);
+
+        // Only broken links are fixed without asking; hand-written skills are no problem
+        let fixes: Vec<Option<Repair>> = entries.iter().map(|e| e.fix()).collect();
+        assert_eq!(fixes, vec![None, None, Some(Repair::Relink), None, None]);
+        assert!(!entries[0].is_problem());
+        assert!(entries[3].is_problem());
+
+        // A copy of a skill that is not installed here still works, so it is left alone
+        fs::create_dir_all(skills.join("other:tools:docx")).unwrap();
+        fs::write(
+            skills.join("other:tools:docx").join(copy::RECORD_FILE),
+            "skill = \"other:tools:docx\"\nhash = \"\"\n",
+        )
+        .unwrap();
+        let entries = diagnose(&plugins, std::slice::from_ref(&target)).unwrap();
+        let copied = entries
+            .iter()
+            .find(|e| e.name() == "other:tools:docx")
+            .unwrap();
+        assert_eq!(copied.health, Health::NotInstalled);
+        assert_eq!(copied.skill.as_deref(), Some("other:tools:docx"));
+        assert!(copied.managed);
+        assert_eq!(copied.fix(), None);
+        assert!(!copied.is_problem());
+        fs::remove_dir_all(skills.join("other:tools:docx")).unwrap();
+
+        // A hand-made copy of an installed skill is recognized by its content
+        fs::write(skills.join("by-hand").join("SKILL.md"), "# xlsx").unwrap();
+        let entries = diagnose(&plugins, std::slice::from_ref(&target)).unwrap();
+        assert_eq!(
+            entries[0].origin,
+            Some(Origin::Skill("me:tools:xlsx".to_string()))
+        );
+        assert_eq!(entries[0].repairs(), vec![Repair::Replace, Repair::Adopt]);
+    }
+}
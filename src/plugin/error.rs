@@ -12,9 +12,46 @@ pub enum PluginError {
     #[error("update failed for {}: {stderr}", path.display())]
     UpdateFailed { path: PathBuf, stderr: String },
 
+    #[error("git command failed in {}: {stderr}", path.display())]
+    GitFailed { path: PathBuf, stderr: String },
+
+    #[error("pinned commit {commit} no longer exists upstream for {url}")]
+    PinnedCommitMissing { url: String, commit: String },
+
+    #[error("{name} is already installed at {installed}; remove it first to install {requested}")]
+    RefMismatch {
+        name: String,
+        installed: String,
+        requested: String,
+    },
+
+    #[error("plugin not in lockfile: {name}")]
+    NotLocked { name: String },
+
+    #[error("invalid lockfile {}: {reason}", path.display())]
+    InvalidLockfile { path: PathBuf, reason: String },
+
+    #[error("subdirectory {subdir} not found in {url}")]
+    SubdirNotFound { url: String, subdir: String },
+
+    #[error("local directory not found: {}", path.display())]
+    LocalPathNotFound { path: PathBuf },
+
+    #[error("a local plugin named {name} is already registered for {}", path.display())]
+    LocalNameTaken { name: String, path: PathBuf },
+
+    #[error("no previous revision recorded for {name}")]
+    NoPreviousRevision { name: String },
+
     #[error("plugin not installed: {name}")]
     NotInstalled { name: String },
 
+    #[error("plugin name is ambiguous: {name} (matches {candidates})")]
+    AmbiguousName { name: String, candidates: String },
+
+    #[error("skill not found: {name}")]
+    SkillNotFound { name: String },
+
     #[error("failed to link skill {name}: {reason}")]
     LinkFailed { name: String, reason: String },
 
@@ -24,6 +61,50 @@ pub enum PluginError {
     #[error("skill not linked: {name}")]
     NotLinked { name: String },
 
+    #[error(
+        "the copy of {name} in {target} was edited locally; remove it with skir unlink to discard the edits"
+    )]
+    CopyModified { name: String, target: String },
+
+    #[error("{name} cannot be linked to {target}")]
+    NotLinkable { name: String, target: String },
+
+    #[error("lint found {errors} errors")]
+    LintFailed { errors: usize },
+
+    #[error("doctor found {problems} problems")]
+    UnhealthyLinks { problems: usize },
+
+    #[error("adopting failed for {failures} entries")]
+    AdoptFailed { failures: usize },
+
+    #[error("cannot {repair} {}", path.display())]
+    RepairNotApplicable { repair: &'static str, path: PathBuf },
+
+    #[error("invalid manifest {}: {reason}", path.display())]
+    InvalidManifest { path: PathBuf, reason: String },
+
+    #[error("invalid marketplace {}: {reason}", path.display())]
+    InvalidMarketplace { path: PathBuf, reason: String },
+
+    #[error("a marketplace named {name} is already added from {from}")]
+    MarketplaceExists { name: String, from: String },
+
+    #[error("marketplace not found: {name}")]
+    MarketplaceNotFound { name: String },
+
+    #[error("no marketplace lists {name}")]
+    EntryNotFound { name: String },
+
+    #[error("cannot install {name}: {reason}")]
+    UnsupportedSource { name: String, reason: String },
+
+    #[error("invalid config {}: {reason}", path.display())]
+    InvalidConfig { path: PathBuf, reason: String },
+
+    #[error("unknown link target: {name} (available: {available})")]
+    UnknownTarget { name: String, available: String },
+
     #[error("cache directory not found")]
     CacheDirectoryNotFound,
 
@@ -4,16 +4,29 @@ use std::process::Command;
 use super::error::PluginError;
 
 /// Clone a git repository to the specified destination.
-pub fn git_clone(url: &str, dest: &Path) -> Result<(), PluginError> {
+///
+/// Checks out `branch` (a branch or tag name) when given, otherwise the default branch.
+/// With `sparse`, only that subdirectory is checked out and blobs outside it are not fetched.
+pub fn git_clone(
+    url: &str,
+    dest: &Path,
+    branch: Option<&str>,
+    sparse: Option<&str>,
+) -> Result<(), PluginError> {
     // Create parent directories if they don't exist
     if let Some(parent) = dest.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    let output = Command::new("git")
-        .args(["clone", "--depth", "1", url])
-        .arg(dest)
-        .output()?;
+    let mut command = Command::new("git");
+    command.args(["clone", "--depth", "1"]);
+    if let Some(branch) = branch {
+        command.args(["--branch", branch]);
+    }
+    if sparse.is_some() {
+        command.args(["--filter=blob:none", "--sparse"]);
+    }
+    let output = command.arg(url).arg(dest).output()?;
 
     if !output.status.success() {
         return Err(PluginError::CloneFailed {
@@ -22,16 +35,44 @@ pub fn git_clone(url: &str, dest: &Path) -> Result<(), PluginError> {
         });
     }
 
+    if let Some(subdir) = sparse {
+        let output = Command::new("git")
+            .args(["sparse-checkout", "set", "--cone", subdir])
+            .current_dir(dest)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(PluginError::CloneFailed {
+                url: url.to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+    }
+
     Ok(())
 }
 
-/// Pull the latest changes in a git repository.
-pub fn git_pull(path: &Path) -> Result<(), PluginError> {
+/// Get the commit SHA currently checked out in a git repository.
+pub fn git_head(path: &Path) -> Result<String, PluginError> {
     let output = Command::new("git")
-        .args(["pull", "--ff-only"])
+        .args(["rev-parse", "HEAD"])
         .current_dir(path)
         .output()?;
 
+    if !output.status.success() {
+        return Err(PluginError::GitFailed {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Run a git command and return its stdout, mapping failure to `UpdateFailed`.
+fn git_update_output(path: &Path, args: &[&str]) -> Result<String, PluginError> {
+    let output = Command::new("git").args(args).current_dir(path).output()?;
+
     if !output.status.success() {
         return Err(PluginError::UpdateFailed {
             path: path.to_path_buf(),
@@ -39,6 +80,174 @@ pub fn git_pull(path: &Path) -> Result<(), PluginError> {
         });
     }
 
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Fetch from `origin` without touching the working tree.
+///
+/// Returns the commit the current branch's upstream now points to.
+pub fn git_fetch(path: &Path) -> Result<String, PluginError> {
+    git_update_output(path, &["fetch", "--quiet", "origin"])?;
+    let upstream = git_update_output(path, &["rev-parse", "@{upstream}"])?;
+    Ok(upstream.trim().to_string())
+}
+
+/// List commits in `from..to` as `<sha>\t<summary>` lines, newest first.
+pub fn git_log(path: &Path, from: &str, to: &str) -> Result<String, PluginError> {
+    git_update_output(
+        path,
+        &["log", "--format=%H%x09%s", &format!("{}..{}", from, to)],
+    )
+}
+
+/// Count the commits in `from..to`.
+pub fn git_count_commits(path: &Path, from: &str, to: &str) -> Result<usize, PluginError> {
+    let count = git_update_output(path, &["rev-list", "--count", &format!("{}..{}", from, to)])?;
+    count.trim().parse().map_err(|_| PluginError::UpdateFailed {
+        path: path.to_path_buf(),
+        stderr: format!("unexpected rev-list output: {}", count.trim()),
+    })
+}
+
+/// Diff two commits, limited to `pathspec`.
+pub fn git_diff(path: &Path, from: &str, to: &str, pathspec: &str) -> Result<String, PluginError> {
+    git_update_output(path, &["diff", "--no-color", from, to, "--", pathspec])
+}
+
+/// Fast-forward the current branch to `commit`.
+pub fn git_fast_forward(path: &Path, commit: &str) -> Result<(), PluginError> {
+    git_update_output(path, &["merge", "--ff-only", "--quiet", commit])?;
+    Ok(())
+}
+
+/// Get the URL of the `origin` remote.
+pub fn git_remote_url(path: &Path) -> Result<String, PluginError> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(PluginError::GitFailed {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Check if the repository at `url` has a tag named `name`.
+pub fn git_remote_has_tag(url: &str, name: &str) -> bool {
+    Command::new("git")
+        .args(["ls-remote", "--tags", url, &format!("refs/tags/{}", name)])
+        .output()
+        .map(|o| o.status.success() && !o.stdout.is_empty())
+        .unwrap_or(false)
+}
+
+/// Check if the repository at `url` has a branch named `name`.
+pub fn git_remote_has_branch(url: &str, name: &str) -> bool {
+    Command::new("git")
+        .args(["ls-remote", "--heads", url, &format!("refs/heads/{}", name)])
+        .output()
+        .map(|o| o.status.success() && !o.stdout.is_empty())
+        .unwrap_or(false)
+}
+
+/// Get the root of the git work tree containing `dir`, if any.
+pub fn git_toplevel(dir: &Path) -> Option<std::path::PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().into())
+}
+
+/// Read a value from the repository's local git config.
+pub fn git_config_get(path: &Path, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--local", "--get", key])
+        .current_dir(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Write a value to the repository's local git config.
+pub fn git_config_set(path: &Path, key: &str, value: &str) -> Result<(), PluginError> {
+    let output = Command::new("git")
+        .args(["config", "--local", key, value])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(PluginError::GitFailed {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check if a commit object is present in the local repository.
+fn git_has_commit(path: &Path, commit: &str) -> bool {
+    Command::new("git")
+        .args(["cat-file", "-e", &format!("{}^{{commit}}", commit)])
+        .current_dir(path)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Run a git command, ignoring its outcome.
+fn git_try(path: &Path, args: &[&str]) {
+    let _ = Command::new("git").args(args).current_dir(path).output();
+}
+
+/// Check out an exact commit, fetching it from `origin` if needed.
+///
+/// Clones are shallow, so the commit is first fetched directly, then by
+/// deepening the history. The current branch (if any) is reset to the commit,
+/// so a later `git pull --ff-only` can still move it forward.
+pub fn git_checkout_commit(path: &Path, commit: &str) -> Result<(), PluginError> {
+    if !git_has_commit(path, commit) {
+        git_try(path, &["fetch", "--depth", "1", "origin", commit]);
+    }
+    if !git_has_commit(path, commit) {
+        git_try(path, &["fetch", "--unshallow", "--tags", "origin"]);
+    }
+    if !git_has_commit(path, commit) {
+        let url = git_remote_url(path).unwrap_or_else(|_| path.display().to_string());
+        return Err(PluginError::PinnedCommitMissing {
+            url,
+            commit: commit.to_string(),
+        });
+    }
+
+    let output = Command::new("git")
+        .args(["reset", "--hard", commit])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(PluginError::GitFailed {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
     Ok(())
 }
 
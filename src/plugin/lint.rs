@@ -0,0 +1,340 @@
+//! Validation of SKILL.md files.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::error::PluginError;
+use super::metadata::{SkillMetadata, split_frontmatter};
+use super::plugin::scan_for_skills;
+
+/// Longest allowed skill name.
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Longest allowed skill description.
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The skill is broken and may not load.
+    Error,
+    /// The skill loads but something looks wrong.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a SKILL.md file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The SKILL.md file.
+    pub path: PathBuf,
+    /// 1-based line number, if the problem is tied to a line.
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    /// Formats as `path:line: severity: message`, like compiler output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => {
+                write!(
+                    f,
+                    "{}:{}: {}: {}",
+                    self.path.display(),
+                    line,
+                    self.severity,
+                    self.message
+                )
+            }
+            None => write!(
+                f,
+                "{}: {}: {}",
+                self.path.display(),
+                self.severity,
+                self.message
+            ),
+        }
+    }
+}
+
+/// Collects diagnostics for one file.
+struct Linter<'a> {
+    path: &'a Path,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, severity: Severity, line: Option<usize>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            path: self.path.to_path_buf(),
+            line,
+            severity,
+            message: message.into(),
+        });
+    }
+}
+
+/// Find the line a top-level frontmatter key is defined on.
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    let (yaml, first_line) = split_frontmatter(content)?.ok()?;
+    let prefix = format!("{}:", key);
+    yaml.lines()
+        .position(|line| line.starts_with(&prefix))
+        .map(|i| first_line + i)
+}
+
+/// Check if a name only uses lowercase letters, digits and hyphens.
+fn is_valid_name(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Extract the targets of inline markdown links and images on a line.
+fn link_targets(line: &str) -> Vec<&str> {
+    let mut targets = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find(')') else { break };
+        // Drop an optional title: [text](target "title")
+        let target = rest[..end].split_whitespace().next().unwrap_or("");
+        targets.push(target.trim_start_matches('<').trim_end_matches('>'));
+        rest = &rest[end..];
+    }
+    targets
+}
+
+/// Check if a link target refers to a local file.
+fn is_local_link(target: &str) -> bool {
+    !target.is_empty()
+        && !target.starts_with('#')
+        && !target.contains("://")
+        && !target.starts_with("mailto:")
+}
+
+/// Check the frontmatter fields of a skill.
+fn lint_metadata(linter: &mut Linter, name: &str, content: &str, metadata: &SkillMetadata) {
+    let name_line = key_line(content, "name");
+    match metadata.name.as_deref() {
+        None => linter.report(Severity::Error, Some(1), "missing `name` in frontmatter"),
+        Some(declared) => {
+            if declared != name {
+                linter.report(
+                    Severity::Warning,
+                    name_line,
+                    format!("name `{}` does not match folder `{}`", declared, name),
+                );
+            }
+            if declared.len() > MAX_NAME_LEN {
+                linter.report(
+                    Severity::Warning,
+                    name_line,
+                    format!("name is longer than {} characters", MAX_NAME_LEN),
+                );
+            }
+            if !is_valid_name(declared) {
+                linter.report(
+                    Severity::Warning,
+                    name_line,
+                    "name should only use lowercase letters, digits and hyphens",
+                );
+            }
+        }
+    }
+
+    let description_line = key_line(content, "description");
+    match metadata.description.as_deref().map(str::trim) {
+        None | Some("") => linter.report(
+            Severity::Error,
+            Some(1),
+            "missing `description` in frontmatter",
+        ),
+        Some(description) => {
+            let len = description.chars().count();
+            if len > MAX_DESCRIPTION_LEN {
+                linter.report(
+                    Severity::Error,
+                    description_line,
+                    format!(
+                        "description is {} characters long (at most {})",
+                        len, MAX_DESCRIPTION_LEN
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Check that relative links in the body point to existing files.
+fn lint_links(linter: &mut Linter, content: &str) {
+    let Some(dir) = linter.path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+
+    let mut in_code_block = false;
+    for (i, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        for target in link_targets(line).into_iter().filter(|t| is_local_link(t)) {
+            let file = target
+                .split(['#', '?'])
+                .next()
+                .unwrap_or(target)
+                .replace("%20", " ");
+            if !dir.join(&file).exists() {
+                linter.report(
+                    Severity::Error,
+                    Some(i + 1),
+                    format!("link to missing file `{}`", target),
+                );
+            }
+        }
+    }
+}
+
+/// Read a SKILL.md file and check it.
+///
+/// `name` is the skill name derived from its folder.
+pub(crate) fn lint_skill_file(name: &str, path: &Path) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        path,
+        diagnostics: Vec::new(),
+    };
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            linter.report(Severity::Error, None, e.to_string());
+            return linter.diagnostics;
+        }
+    };
+
+    let blank_lines = content.lines().take_while(|l| l.trim().is_empty()).count();
+    match &SkillMetadata::parse(&content) {
+        _ if split_frontmatter(&content).is_none() => {
+            let starts_later = content
+                .lines()
+                .nth(blank_lines)
+                .is_some_and(|l| l.trim_end() == "---");
+            if blank_lines > 0 && starts_later {
+                linter.report(
+                    Severity::Error,
+                    Some(1),
+                    format!(
+                        "frontmatter must start on the first line, not after {} blank lines",
+                        blank_lines
+                    ),
+                );
+            } else {
+                linter.report(Severity::Error, Some(1), "missing YAML frontmatter");
+            }
+        }
+        Ok(metadata) => lint_metadata(&mut linter, name, &content, metadata),
+        Err(e) => linter.report(Severity::Error, e.line, e.to_string()),
+    }
+    lint_links(&mut linter, &content);
+    linter.diagnostics.sort_by_key(|d| d.line);
+    linter.diagnostics
+}
+
+/// Check every skill found under `root`.
+pub fn lint_dir(root: &Path) -> Result<Vec<Diagnostic>, PluginError> {
+    let mut diagnostics = Vec::new();
+    for (name, path) in scan_for_skills(root)? {
+        diagnostics.extend(lint_skill_file(&name, &path));
+    }
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn lint(name: &str, content: &str) -> Vec<Diagnostic> {
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join(name);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("reference.md"), "").unwrap();
+        let path = skill_dir.join("SKILL.md");
+        fs::write(&path, content).unwrap();
+        lint_skill_file(name, &path)
+    }
+
+    #[test]
+    fn test_lint_clean() {
+        let content = "---\nname: pdf\ndescription: Read PDFs\n---\nSee [the reference](reference.md#usage).\n";
+        assert!(lint("pdf", content).is_empty());
+    }
+
+    #[test]
+    fn test_lint_missing_frontmatter() {
+        let diagnostics = lint("pdf", "# PDF\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_lint_fields() {
+        let long = "x".repeat(MAX_DESCRIPTION_LEN + 1);
+        let content = format!("---\nname: Other_Name\ndescription: {}\n---\n", long);
+        let diagnostics = lint("pdf", &content);
+
+        let messages: Vec<(Severity, Option<usize>)> =
+            diagnostics.iter().map(|d| (d.severity, d.line)).collect();
+        assert_eq!(
+            messages,
+            vec![
+                (Severity::Warning, Some(2)),
+                (Severity::Warning, Some(2)),
+                (Severity::Error, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_frontmatter_after_blank_lines() {
+        let content = "\n\n---\nname: pdf\ndescription: Read PDFs\n---\n";
+        let diagnostics = lint("pdf", content);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(1));
+        assert!(diagnostics[0].message.contains("after 2 blank lines"));
+    }
+
+    #[test]
+    fn test_lint_broken_links() {
+        let content = "\
+---
+name: pdf
+description: Read PDFs
+---
+[ok](reference.md) [missing](scripts/run.py) [web](https://example.com) [anchor](#usage)
+```
+[ignored](in/code/block.md)
+```
+";
+        let diagnostics = lint("pdf", content);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(5));
+        assert!(diagnostics[0].message.contains("scripts/run.py"));
+        assert!(diagnostics[0].to_string().contains("SKILL.md:5: error:"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Lockfile recording the exact commit of each installed plugin.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::PluginError;
+
+/// Default lockfile name.
+pub const LOCKFILE_NAME: &str = "skir.lock";
+
+/// Current lockfile format version.
+const LOCKFILE_VERSION: u32 = 1;
+
+/// Serializes read-modify-write cycles between background threads.
+static LOCKFILE_MUTEX: Mutex<()> = Mutex::new(());
+
+/// Resolved commits for installed plugins, keyed by `host/owner/repo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Format version.
+    pub version: u32,
+    /// Locked plugins.
+    #[serde(default)]
+    pub plugins: BTreeMap<String, LockedPlugin>,
+}
+
+/// A single locked plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPlugin {
+    /// The URL the plugin was cloned from.
+    pub url: String,
+    /// The resolved commit SHA.
+    pub commit: String,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Build the key used for a plugin.
+    pub fn key(host: &str, owner: &str, repo: &str) -> String {
+        format!("{}/{}/{}", host, owner, repo)
+    }
+
+    /// Load a lockfile, returning an empty one if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| PluginError::InvalidLockfile {
+            path: path.to_path_buf(),
+            reason: e.message().to_string(),
+        })
+    }
+
+    /// Write the lockfile to disk.
+    pub fn save(&self, path: &Path) -> Result<(), PluginError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string(self).map_err(|e| PluginError::InvalidLockfile {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Load, modify and save a lockfile in one step.
+    pub fn edit(path: &Path, f: impl FnOnce(&mut Lockfile)) -> Result<(), PluginError> {
+        let _guard = LOCKFILE_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        let mut lockfile = Self::load(path)?;
+        f(&mut lockfile);
+        lockfile.save(path)
+    }
+
+    /// Get the locked entry for a plugin.
+    pub fn get(&self, host: &str, owner: &str, repo: &str) -> Option<&LockedPlugin> {
+        self.plugins.get(&Self::key(host, owner, repo))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_missing_is_empty() {
+        let dir = tempdir().unwrap();
+        let lockfile = Lockfile::load(&dir.path().join(LOCKFILE_NAME)).unwrap();
+        assert_eq!(lockfile, Lockfile::default());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+
+        Lockfile::edit(&path, |lock| {
+            lock.plugins.insert(
+                Lockfile::key("github.com", "anthropics", "skills"),
+                LockedPlugin {
+                    url: "https://github.com/anthropics/skills".to_string(),
+                    commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                },
+            );
+        })
+        .unwrap();
+
+        let lockfile = Lockfile::load(&path).unwrap();
+        let entry = lockfile.get("github.com", "anthropics", "skills").unwrap();
+        assert_eq!(entry.commit, "0123456789abcdef0123456789abcdef01234567");
+        assert!(lockfile.get("github.com", "anthropics", "other").is_none());
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+        fs::write(&path, "not = [valid").unwrap();
+        assert!(matches!(
+            Lockfile::load(&path),
+            Err(PluginError::InvalidLockfile { .. })
+        ));
+    }
+}
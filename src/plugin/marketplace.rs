@@ -0,0 +1,386 @@
+//! Marketplaces: catalogs of plugins published as a `.claude-plugin/marketplace.json`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::component::Author;
+use super::error::PluginError;
+use super::git::git_config_get;
+use super::source::GitRef;
+
+/// Path of the marketplace catalog, relative to the marketplace root.
+pub const MARKETPLACE_MANIFEST: &str = ".claude-plugin/marketplace.json";
+
+/// Git config key recording the branch a marketplace clone follows.
+pub(crate) const MARKETPLACE_REF_KEY: &str = "skir.ref";
+
+/// Where a marketplace entry's plugin comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum EntrySource {
+    /// A directory inside the marketplace repository, e.g. `./plugins/formatter`.
+    Path(String),
+    /// A repository of its own.
+    Remote(RemoteSource),
+}
+
+/// A plugin repository outside the marketplace.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum RemoteSource {
+    /// `owner/repo` on GitHub.
+    Github {
+        repo: String,
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+        sha: Option<String>,
+    },
+    /// Any git URL.
+    #[serde(alias = "git")]
+    Url {
+        url: String,
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+        sha: Option<String>,
+    },
+}
+
+/// A plugin listed in a marketplace.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MarketplaceEntry {
+    pub name: String,
+    pub source: EntrySource,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<Author>,
+}
+
+/// Optional catalog-wide settings.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketplaceMetadata {
+    description: Option<String>,
+    /// Directory that relative plugin sources are resolved against.
+    plugin_root: Option<String>,
+}
+
+/// The contents of `marketplace.json`.
+#[derive(Debug, Deserialize)]
+struct MarketplaceFile {
+    name: String,
+    owner: Option<Author>,
+    #[serde(default)]
+    metadata: MarketplaceMetadata,
+    #[serde(default)]
+    plugins: Vec<MarketplaceEntry>,
+}
+
+/// Where a marketplace was added from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarketplaceSource {
+    /// A git repository, by URL, following a branch if one was given.
+    Git { url: String, branch: Option<String> },
+    /// A local directory, referenced in place.
+    Local(PathBuf),
+}
+
+impl std::fmt::Display for MarketplaceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarketplaceSource::Git { url, branch: None } => write!(f, "{}", url),
+            MarketplaceSource::Git {
+                url,
+                branch: Some(branch),
+            } => write!(f, "{}#{}", url, branch),
+            MarketplaceSource::Local(dir) => write!(f, "{}", dir.display()),
+        }
+    }
+}
+
+/// A marketplace that was added, with the plugins it lists.
+#[derive(Debug, Clone)]
+pub struct Marketplace {
+    /// The catalog's name, which is also its directory name.
+    pub name: String,
+    pub description: Option<String>,
+    pub owner: Option<Author>,
+    pub source: MarketplaceSource,
+    /// The marketplace's clone, or the symlink to its local directory.
+    pub path: PathBuf,
+    plugin_root: Option<String>,
+    pub plugins: Vec<MarketplaceEntry>,
+}
+
+/// Normalize a path relative to the marketplace root, rejecting `..`.
+///
+/// Returns `None` for the root itself.
+fn normalize_relative(path: &str) -> Result<Option<String>, ()> {
+    let parts: Vec<&str> = path
+        .split('/')
+        .filter(|p| !p.is_empty() && *p != ".")
+        .collect();
+    if parts.contains(&"..") {
+        return Err(());
+    }
+    Ok((!parts.is_empty()).then(|| parts.join("/")))
+}
+
+/// Append the ref of a remote source to its URL: an exact `sha` wins over
+/// `ref`, which may be a branch or a tag.
+///
+/// Both are written as an `@` pin; installing turns a pin the repository
+/// only has as a branch into a branch to follow.
+fn with_pin(url: String, reference: &Option<String>, sha: &Option<String>) -> String {
+    match sha.as_ref().or(reference.as_ref()) {
+        Some(pin) => format!("{}@{}", url, pin),
+        None => url,
+    }
+}
+
+impl Marketplace {
+    /// Read the catalog at `dir`, which was added from `source`.
+    pub(crate) fn read(dir: &Path, source: MarketplaceSource) -> Result<Marketplace, PluginError> {
+        let file = dir.join(MARKETPLACE_MANIFEST);
+        let invalid = |reason: String| PluginError::InvalidMarketplace {
+            path: file.clone(),
+            reason,
+        };
+        let content = fs::read_to_string(&file).map_err(|e| invalid(e.to_string()))?;
+        let catalog: MarketplaceFile =
+            serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+
+        // The name becomes a directory name
+        let name = catalog.name.trim();
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(invalid(format!("invalid name {:?}", catalog.name)));
+        }
+
+        Ok(Marketplace {
+            name: name.to_string(),
+            description: catalog.metadata.description,
+            owner: catalog.owner,
+            source,
+            path: dir.to_path_buf(),
+            plugin_root: catalog.metadata.plugin_root,
+            plugins: catalog.plugins,
+        })
+    }
+
+    /// Load an added marketplace from its directory in the marketplaces directory.
+    ///
+    /// Local marketplaces are symlinks; clones record their URL and branch in git config.
+    pub(crate) fn load(path: &Path) -> Result<Marketplace, PluginError> {
+        let source = if path.is_symlink() {
+            MarketplaceSource::Local(fs::read_link(path)?)
+        } else {
+            MarketplaceSource::Git {
+                url: git_config_get(path, "remote.origin.url").unwrap_or_default(),
+                branch: git_config_get(path, MARKETPLACE_REF_KEY)
+                    .and_then(|r| GitRef::from_config(&r))
+                    .map(|r| r.name().to_string()),
+            }
+        };
+        Marketplace::read(path, source)
+    }
+
+    /// Find a listed plugin by name.
+    pub fn entry(&self, name: &str) -> Option<&MarketplaceEntry> {
+        self.plugins.iter().find(|e| e.name == name)
+    }
+
+    /// The subdirectory of the marketplace a relative source points to, or
+    /// `None` for the root. Sources like `formatter` are relative to the
+    /// plugin root; `./formatter` to the marketplace.
+    fn source_subdir(&self, path: &str) -> Result<Option<String>, ()> {
+        let path = match &self.plugin_root {
+            Some(root) if !path.starts_with("./") => format!("{}/{}", root, path),
+            _ => path.to_string(),
+        };
+        normalize_relative(&path)
+    }
+
+    /// The directory of an entry inside this marketplace, for relative sources.
+    pub fn entry_dir(&self, entry: &MarketplaceEntry) -> Option<PathBuf> {
+        let EntrySource::Path(path) = &entry.source else {
+            return None;
+        };
+        let subdir = self.source_subdir(path).ok()?;
+        Some(subdir.map_or_else(|| self.path.clone(), |s| self.path.join(s)))
+    }
+
+    /// The URL or path to install an entry from, as accepted by
+    /// [`PluginManager::install`](super::PluginManager::install).
+    ///
+    /// Relative sources become a subdirectory of the marketplace repository
+    /// (or a directory inside a local marketplace); remote ones are
+    /// installed from their own repository.
+    pub fn install_url(&self, entry: &MarketplaceEntry) -> Result<String, PluginError> {
+        let unsupported = |reason: &str| PluginError::UnsupportedSource {
+            name: entry.name.clone(),
+            reason: reason.to_string(),
+        };
+        let path = match &entry.source {
+            EntrySource::Remote(RemoteSource::Github {
+                repo,
+                reference,
+                sha,
+            }) => {
+                return Ok(with_pin(
+                    format!("https://github.com/{}", repo),
+                    reference,
+                    sha,
+                ));
+            }
+            EntrySource::Remote(RemoteSource::Url {
+                url,
+                reference,
+                sha,
+            }) => {
+                return Ok(with_pin(url.clone(), reference, sha));
+            }
+            EntrySource::Path(path) => path,
+        };
+
+        let subdir = self
+            .source_subdir(path)
+            .map_err(|()| unsupported("source leaves the marketplace"))?;
+
+        match (&self.source, subdir) {
+            (MarketplaceSource::Local(dir), subdir) => {
+                let dir = subdir.map_or_else(|| dir.clone(), |s| dir.join(s));
+                Ok(dir.display().to_string())
+            }
+            (MarketplaceSource::Git { url, .. }, _) if url.is_empty() => {
+                Err(unsupported("marketplace has no remote"))
+            }
+            (MarketplaceSource::Git { url, branch }, subdir) => {
+                let url = match subdir {
+                    Some(subdir) => format!("{}//{}", url, subdir),
+                    None => url.clone(),
+                };
+                Ok(match branch {
+                    Some(branch) => format!("{}#{}", url, branch),
+                    None => url,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::GitSource;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_read_marketplace() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".claude-plugin")).unwrap();
+        fs::write(
+            dir.path().join(MARKETPLACE_MANIFEST),
+            r#"{
+                "name": "team-tools",
+                "owner": {"name": "Team"},
+                "metadata": {"description": "Our plugins", "pluginRoot": "./plugins"},
+                "plugins": [
+                    {"name": "formatter", "source": "formatter", "description": "Formats code", "version": "2.1.0"},
+                    {"name": "local", "source": "./extra/local"},
+                    {"name": "deploy", "source": {"source": "github", "repo": "team/deploy", "ref": "stable"}},
+                    {"name": "lint", "source": {"source": "url", "url": "https://gitlab.com/team/lint.git"}},
+                    {"name": "release", "source": {"source": "github", "repo": "team/release", "ref": "v1.0.0"}},
+                    {"name": "exact", "source": {"source": "url", "url": "https://gitlab.com/team/exact.git",
+                        "ref": "main", "sha": "0123456789abcdef0123456789abcdef01234567"}},
+                    {"name": "escape", "source": "../elsewhere"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let source = MarketplaceSource::Git {
+            url: "https://github.com/team/marketplace".to_string(),
+            branch: Some("main".to_string()),
+        };
+        let marketplace = Marketplace::read(dir.path(), source).unwrap();
+        assert_eq!(marketplace.name, "team-tools");
+        assert_eq!(marketplace.description.as_deref(), Some("Our plugins"));
+        assert_eq!(marketplace.plugins.len(), 7);
+
+        let formatter = marketplace.entry("formatter").unwrap();
+        assert_eq!(formatter.version.as_deref(), Some("2.1.0"));
+        let url = |name: &str| marketplace.install_url(marketplace.entry(name).unwrap());
+        assert_eq!(
+            url("formatter").unwrap(),
+            "https://github.com/team/marketplace//plugins/formatter#main"
+        );
+        assert_eq!(
+            url("local").unwrap(),
+            "https://github.com/team/marketplace//extra/local#main"
+        );
+        assert_eq!(
+            url("deploy").unwrap(),
+            "https://github.com/team/deploy@stable"
+        );
+        assert_eq!(url("lint").unwrap(), "https://gitlab.com/team/lint.git");
+        assert_eq!(
+            url("release").unwrap(),
+            "https://github.com/team/release@v1.0.0"
+        );
+        assert_eq!(
+            url("exact").unwrap(),
+            "https://gitlab.com/team/exact.git@0123456789abcdef0123456789abcdef01234567"
+        );
+        // Tags stay pinned, commits are exact
+        let reference = |name: &str| GitSource::parse(&url(name).unwrap()).unwrap().reference;
+        assert_eq!(
+            reference("release"),
+            Some(GitRef::Tag("v1.0.0".to_string()))
+        );
+        assert_eq!(
+            reference("exact"),
+            Some(GitRef::Commit(
+                "0123456789abcdef0123456789abcdef01234567".to_string()
+            ))
+        );
+        assert!(url("escape").is_err());
+        assert_eq!(
+            marketplace.entry_dir(formatter),
+            Some(dir.path().join("plugins/formatter"))
+        );
+        assert_eq!(
+            marketplace.entry_dir(marketplace.entry("deploy").unwrap()),
+            None
+        );
+
+        // Local marketplaces point at directories instead
+        let local = Marketplace::read(
+            dir.path(),
+            MarketplaceSource::Local(dir.path().to_path_buf()),
+        )
+        .unwrap();
+        let expected = dir.path().join("plugins/formatter");
+        assert_eq!(
+            local
+                .install_url(local.entry("formatter").unwrap())
+                .unwrap(),
+            expected.display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_marketplace() {
+        let dir = tempdir().unwrap();
+        let source = MarketplaceSource::Local(dir.path().to_path_buf());
+        assert!(Marketplace::read(dir.path(), source.clone()).is_err());
+
+        fs::create_dir_all(dir.path().join(".claude-plugin")).unwrap();
+        fs::write(
+            dir.path().join(MARKETPLACE_MANIFEST),
+            r#"{"name": "../up", "plugins": []}"#,
+        )
+        .unwrap();
+        assert!(Marketplace::read(dir.path(), source).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! SKILL.md frontmatter parsing.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+/// Metadata from the YAML frontmatter of a SKILL.md file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub version: Option<String>,
+    pub license: Option<String>,
+    /// Tools the skill may use, given either as a list or a comma-separated string.
+    #[serde(default, deserialize_with = "string_list")]
+    pub allowed_tools: Vec<String>,
+    /// Any other keys, kept as they were written.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Frontmatter that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct FrontmatterError {
+    /// 1-based line in SKILL.md the problem was found at, if known.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "invalid frontmatter at line {}: {}", line, self.message),
+            None => write!(f, "invalid frontmatter: {}", self.message),
+        }
+    }
+}
+
+/// Accept `version: 1.2` as well as `version: "1.2"`.
+fn string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Version {
+        String(String),
+        Integer(i64),
+        Float(f64),
+    }
+
+    Ok(
+        Option::<Version>::deserialize(deserializer)?.map(|v| match v {
+            Version::String(s) => s,
+            Version::Integer(n) => n.to_string(),
+            Version::Float(n) => n.to_string(),
+        }),
+    )
+}
+
+/// Accept a YAML list or a comma-separated string.
+fn string_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        String(String),
+        List(Vec<String>),
+    }
+
+    Ok(match Option::<List>::deserialize(deserializer)? {
+        Some(List::String(s)) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        Some(List::List(list)) => list,
+        None => Vec::new(),
+    })
+}
+
+/// Find the frontmatter block of a SKILL.md file.
+///
+/// Returns the YAML text and the 1-based line number of its first line, or
+/// `None` if the first line of the file is not `---`. Like Claude Code, this
+/// does not look past leading blank lines.
+pub(crate) fn split_frontmatter(
+    content: &str,
+) -> Option<Result<(String, usize), FrontmatterError>> {
+    let mut lines = content.lines().enumerate();
+    let (open, first) = lines.next()?;
+    if first.trim_end() != "---" {
+        return None;
+    }
+
+    let mut yaml = String::new();
+    for (_, line) in lines {
+        if line.trim_end() == "---" || line.trim_end() == "..." {
+            return Some(Ok((yaml, open + 2)));
+        }
+        yaml.push_str(line);
+        yaml.push('\n');
+    }
+
+    Some(Err(FrontmatterError {
+        line: Some(open + 1),
+        message: "missing closing `---`".to_string(),
+    }))
+}
+
+impl SkillMetadata {
+    /// Read and parse the frontmatter of a SKILL.md file.
+    pub fn read(path: &Path) -> Result<SkillMetadata, FrontmatterError> {
+        let content = fs::read_to_string(path).map_err(|e| FrontmatterError {
+            line: None,
+            message: e.to_string(),
+        })?;
+        SkillMetadata::parse(&content)
+    }
+
+    /// Parse the frontmatter of a SKILL.md file.
+    ///
+    /// A file without frontmatter has empty metadata.
+    pub fn parse(content: &str) -> Result<SkillMetadata, FrontmatterError> {
+        let (yaml, first_line) = match split_frontmatter(content) {
+            None => return Ok(SkillMetadata::default()),
+            Some(result) => result?,
+        };
+        if yaml.trim().is_empty() {
+            return Ok(SkillMetadata::default());
+        }
+
+        serde_norway::from_str(&yaml).map_err(|e| {
+            let mut message = e.to_string();
+            let line = e.location().map(|location| {
+                // The location is relative to the frontmatter; report it relative to the
+                // file instead
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                if let Some(stripped) = message.strip_suffix(&suffix) {
+                    message = stripped.to_string();
+                }
+                location.line() + first_line - 1
+            });
+            FrontmatterError { line, message }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full() {
+        let content = "\
+---
+name: pdf
+description: >
+  Extract text
+  from PDFs.
+version: 1.2
+license: MIT
+allowed-tools: Read, Grep
+category: documents
+---
+# PDF
+";
+        let metadata = SkillMetadata::parse(content).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("pdf"));
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Extract text from PDFs.\n")
+        );
+        assert_eq!(metadata.version.as_deref(), Some("1.2"));
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(metadata.allowed_tools, vec!["Read", "Grep"]);
+        assert_eq!(metadata.extra["category"], "documents");
+    }
+
+    #[test]
+    fn test_parse_literal_block_and_list() {
+        let content = "---\ndescription: |\n  Line one\n  Line two\nallowed-tools:\n  - Bash\n  - Edit\n---\n";
+        let metadata = SkillMetadata::parse(content).unwrap();
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Line one\nLine two\n")
+        );
+        assert_eq!(metadata.allowed_tools, vec!["Bash", "Edit"]);
+    }
+
+    #[test]
+    fn test_parse_without_frontmatter() {
+        assert_eq!(
+            SkillMetadata::parse("# Just markdown\n").unwrap(),
+            SkillMetadata::default()
+        );
+        assert_eq!(
+            SkillMetadata::parse("---\n---\n").unwrap(),
+            SkillMetadata::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_frontmatter_after_blank_lines() {
+        assert_eq!(
+            SkillMetadata::parse("\n---\nname: pdf\n---\n").unwrap(),
+            SkillMetadata::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let err =
+            SkillMetadata::parse("---\nname: pdf\ndescription: [unclosed\n---\n").unwrap_err();
+        assert!(err.line.is_some());
+
+        let err = SkillMetadata::parse("---\nname: pdf\n").unwrap_err();
+        assert_eq!(err.line, Some(1));
+
+        let err =
+            SkillMetadata::parse("---\nname: pdf\ndescription:\n  nested: map\n---\n").unwrap_err();
+        assert!(err.message.contains("description"));
+    }
+}
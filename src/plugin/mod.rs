@@ -1,12 +1,45 @@
+mod component;
+mod config;
+mod copy;
+mod doctor;
 mod error;
 mod git;
+mod lint;
+mod lockfile;
 mod manager;
+mod marketplace;
+mod metadata;
 mod plugin;
+mod pool;
+mod search;
 mod skill;
 mod source;
+mod target;
+#[cfg(test)]
+pub(crate) mod testing;
+mod update;
 
+pub use component::{
+    Author, Component, ComponentKind, PLUGIN_MANIFEST, PathOrInline, Paths, PluginManifest,
+};
+pub use config::{
+    CONFIG_FILE, Config, DEFAULT_HOST, Setting, Settings, Source, TargetConfig, UiConfig,
+};
+pub use doctor::{Health, LinkEntry, Origin, Repair, diagnose};
 pub use error::PluginError;
-pub use manager::PluginManager;
-pub use plugin::Plugin;
-pub use skill::{LinkTarget, Skill};
-pub use source::GitSource;
+pub use lint::{Diagnostic, MAX_DESCRIPTION_LEN, MAX_NAME_LEN, Severity, lint_dir};
+pub use lockfile::{LOCKFILE_NAME, LockedPlugin, Lockfile};
+pub use manager::{
+    DEFAULT_CONCURRENCY, FetchAllReceiver, Fetched, PluginManager, UpdateAllReceiver,
+};
+pub use marketplace::{
+    EntrySource, MARKETPLACE_MANIFEST, Marketplace, MarketplaceEntry, MarketplaceSource,
+    RemoteSource,
+};
+pub use metadata::{FrontmatterError, SkillMetadata};
+pub use plugin::{Plugin, find_skill};
+pub use search::{SearchDoc, SearchIndex, SearchResult, SearchSource};
+pub use skill::{LinkStatus, Skill};
+pub use source::{GitRef, GitSource, parse_local_path};
+pub use target::{LinkMode, LinkTarget, Naming};
+pub use update::{FileChange, FileStatus, IncomingCommit, PendingUpdate, SkillChange};
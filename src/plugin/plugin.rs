@@ -1,11 +1,20 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use super::component::{Bundle, Component, ComponentKind, PluginManifest};
 use super::error::PluginError;
-use super::git::{git_clone, git_pull, is_git_repo};
-use super::skill::{self, Skill};
-use super::source::GitSource;
+use super::git::{
+    git_checkout_commit, git_clone, git_config_get, git_config_set, git_count_commits, git_diff,
+    git_fast_forward, git_fetch, git_head, git_log, git_remote_has_branch, git_remote_has_tag,
+    git_remote_url, is_git_repo,
+};
+use super::skill::Skill;
+use super::source::{GitRef, GitSource, scoped_name};
+use super::target::{LinkMode, LinkTarget};
+use super::update::{PendingUpdate, group_by_skill, parse_diff, parse_log};
 
 /// Extract the directory name from a path as a String.
 fn dir_name(path: &Path) -> Option<String> {
@@ -68,6 +77,121 @@ fn derive_skill_name(root: &Path, skill_path: &Path) -> String {
     }
 }
 
+/// Host directory under which local plugins are registered.
+pub const LOCAL_HOST: &str = "local";
+
+/// Owner given to all local plugins.
+pub const LOCAL_OWNER: &str = "local";
+
+/// Git config key recording the ref a clone is pinned to.
+const REF_CONFIG_KEY: &str = "skir.ref";
+
+/// Git config key recording the subdirectory a sparse clone is scoped to.
+const SUBDIR_CONFIG_KEY: &str = "skir.subdir";
+
+/// Git config key recording the commits a clone was at before recent updates.
+const HISTORY_CONFIG_KEY: &str = "skir.history";
+
+/// Number of previous revisions kept per plugin.
+const HISTORY_LEN: usize = 5;
+
+/// Suffix of the directory a plugin is cloned into again by [`Plugin::switch_ref`].
+pub(crate) const STAGING_SUFFIX: &str = ".skir-tmp";
+
+/// Read the subdirectory recorded in a clone's git config.
+fn read_subdir(path: &Path) -> Option<String> {
+    if !is_git_repo(path) {
+        return None;
+    }
+    git_config_get(path, SUBDIR_CONFIG_KEY)
+}
+
+/// Recover the repository name from a plugin directory name.
+///
+/// Directories of subdirectory plugins carry the scoped name (see [`scoped_name`]).
+pub(crate) fn repo_for_dir(dir_name: &str, path: &Path) -> String {
+    match read_subdir(path) {
+        Some(subdir) => {
+            let suffix = scoped_name("", Some(&subdir));
+            dir_name
+                .strip_suffix(&suffix)
+                .unwrap_or(dir_name)
+                .to_string()
+        }
+        None => dir_name.to_string(),
+    }
+}
+
+/// Read the pinned ref recorded in a clone's git config.
+fn read_reference(path: &Path) -> Option<GitRef> {
+    if !is_git_repo(path) {
+        return None;
+    }
+    git_config_get(path, REF_CONFIG_KEY).and_then(|value| GitRef::from_config(&value))
+}
+
+/// Clone a source and check out its ref and subdirectory, recording both in
+/// the clone's git config.
+///
+/// The clone is removed again if the ref or subdirectory cannot be checked out.
+fn clone_source(source: &GitSource, path: &Path) -> Result<(), PluginError> {
+    let reference = resolve_reference(source);
+    let branch = match &reference {
+        Some(GitRef::Branch(name)) | Some(GitRef::Tag(name)) => Some(name.as_str()),
+        Some(GitRef::Commit(_)) | None => None,
+    };
+    git_clone(&source.url, path, branch, source.subdir.as_deref())?;
+
+    let configured = configure_clone(source, reference.as_ref(), path);
+    if configured.is_err() {
+        let _ = fs::remove_dir_all(path);
+    }
+    configured
+}
+
+/// Treat a pin that looks like a commit as a tag if the remote has a tag by
+/// that name, and a tag as a branch if the remote only has a branch by that name.
+fn resolve_reference(source: &GitSource) -> Option<GitRef> {
+    match &source.reference {
+        Some(GitRef::Commit(name)) if git_remote_has_tag(&source.url, name) => {
+            Some(GitRef::Tag(name.clone()))
+        }
+        Some(GitRef::Tag(name))
+            if !git_remote_has_tag(&source.url, name)
+                && git_remote_has_branch(&source.url, name) =>
+        {
+            Some(GitRef::Branch(name.clone()))
+        }
+        reference => reference.clone(),
+    }
+}
+
+/// Check out the resolved ref's commit and record the ref and subdirectory.
+fn configure_clone(
+    source: &GitSource,
+    reference: Option<&GitRef>,
+    path: &Path,
+) -> Result<(), PluginError> {
+    if let Some(reference) = reference {
+        if let GitRef::Commit(sha) = reference {
+            git_checkout_commit(path, sha)?;
+        }
+        git_config_set(path, REF_CONFIG_KEY, &reference.to_config())?;
+    }
+
+    if let Some(subdir) = &source.subdir {
+        if !path.join(subdir).is_dir() {
+            return Err(PluginError::SubdirNotFound {
+                url: source.url.clone(),
+                subdir: subdir.clone(),
+            });
+        }
+        git_config_set(path, SUBDIR_CONFIG_KEY, subdir)?;
+    }
+
+    Ok(())
+}
+
 /// A plugin with its identifier and discovered skills.
 #[derive(Debug)]
 pub struct Plugin {
@@ -79,8 +203,18 @@ pub struct Plugin {
     pub repo: String,
     /// The local path where the plugin is installed.
     pub path: PathBuf,
+    /// The branch, tag or commit this plugin is pinned to, if any.
+    pub reference: Option<GitRef>,
+    /// The subdirectory this plugin is scoped to, if any.
+    pub subdir: Option<String>,
     /// Skills discovered in this plugin (populated after Arc creation).
     skills: Vec<Skill>,
+    /// The `.claude-plugin/plugin.json` manifest, if the plugin has one.
+    pub manifest: Option<PluginManifest>,
+    /// Commands, agents, hooks and MCP servers shipped alongside the skills.
+    components: Vec<Component>,
+    /// Bundle files that could not be read or parsed, with the reason.
+    pub bundle_errors: Vec<String>,
 }
 
 impl Plugin {
@@ -91,46 +225,174 @@ impl Plugin {
             owner,
             repo,
             path,
+            reference: None,
+            subdir: None,
             skills: Vec::new(),
+            manifest: None,
+            components: Vec::new(),
+            bundle_errors: Vec::new(),
         }
     }
 
-    /// Build a new Plugin by scanning for skills at the given path.
+    /// Build a new Plugin by scanning for skills and bundle components at the given path.
+    ///
+    /// Plugins scoped to a subdirectory only scan that subdirectory.
     pub(crate) fn build(
         host: String,
         owner: String,
         repo: String,
         path: PathBuf,
     ) -> Result<Plugin, PluginError> {
-        let skill_paths = scan_for_skills(&path)?;
+        let mut plugin = Plugin::new(host, owner, repo, path);
+        // A local directory may be the user's own git checkout; its config is not ours
+        if !plugin.is_local() {
+            plugin.reference = read_reference(&plugin.path);
+            plugin.subdir = read_subdir(&plugin.path);
+        }
+
+        let root = plugin.skills_root();
+        let skill_paths = if root.is_dir() {
+            scan_for_skills(&root)?
+        } else {
+            // The subdirectory disappeared upstream
+            Vec::new()
+        };
+        let name = plugin.name().to_string();
         let skills: Vec<Skill> = skill_paths
             .into_iter()
-            .map(|(name, skill_path)| Skill::new(name, skill_path, owner.clone(), repo.clone()))
+            .map(|(skill_name, skill_path)| {
+                Skill::new(
+                    skill_name,
+                    skill_path,
+                    plugin.owner.clone(),
+                    name.clone(),
+                    plugin.path.clone(),
+                )
+            })
             .collect();
 
-        let mut plugin = Plugin::new(host, owner, repo, path);
         plugin.set_skills(skills);
+
+        if root.is_dir() {
+            let bundle = Bundle::discover(&root, &plugin.owner, &name);
+            plugin.manifest = bundle.manifest;
+            plugin.components = bundle.components;
+            plugin.bundle_errors = bundle.errors;
+        }
         Ok(plugin)
     }
 
+    /// The directory scanned for skills: the subdirectory, if any, else the whole checkout.
+    pub fn skills_root(&self) -> PathBuf {
+        match &self.subdir {
+            Some(subdir) => self.path.join(subdir),
+            None => self.path.clone(),
+        }
+    }
+
     /// Install a plugin by cloning (or updating) the repository and scanning for skills.
     ///
-    /// If the path already contains a git repo, pulls latest changes instead of cloning.
-    pub fn install(source: GitSource, path: PathBuf) -> Result<Plugin, PluginError> {
+    /// If the path already contains a git repo, updates it instead of cloning.
+    /// Asking for a different ref than the one installed is an error.
+    pub fn install(
+        source: GitSource,
+        path: PathBuf,
+        targets: &[LinkTarget],
+    ) -> Result<Plugin, PluginError> {
         if is_git_repo(&path) {
-            // Already installed, update instead
-            git_pull(&path)?;
-        } else {
-            // Clone the repository
-            git_clone(&source.url, &path)?;
+            // Already installed, update instead, unless another ref was asked for
+            let plugin = Plugin::build(source.host, source.owner, source.repo, path)?;
+            if let Some(requested) = &source.reference {
+                if !plugin.is_at_ref(Some(requested)) {
+                    return Err(PluginError::RefMismatch {
+                        name: format!("{}/{}", plugin.owner, plugin.name()),
+                        installed: plugin
+                            .reference
+                            .as_ref()
+                            .map_or_else(|| "the default branch".to_string(), GitRef::to_string),
+                        requested: requested.to_string(),
+                    });
+                }
+            }
+            return plugin.update(targets);
         }
 
+        clone_source(&source, &path)?;
         Plugin::build(source.host, source.owner, source.repo, path)
     }
 
-    /// The plugin name (derived from the repository name).
-    pub fn name(&self) -> &str {
-        &self.repo
+    /// Register a local directory as a plugin without copying it.
+    ///
+    /// `path` becomes a symlink to `dir`. Registering the same directory again
+    /// just rescans it.
+    pub fn install_local(dir: PathBuf, path: PathBuf) -> Result<Plugin, PluginError> {
+        let name =
+            dir_name(&dir).ok_or_else(|| PluginError::LocalPathNotFound { path: dir.clone() })?;
+
+        if path.symlink_metadata().is_ok() {
+            let existing = fs::read_link(&path)?;
+            if existing != dir {
+                return Err(PluginError::LocalNameTaken {
+                    name,
+                    path: existing,
+                });
+            }
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&dir, &path)?;
+
+            #[cfg(windows)]
+            std::os::windows::fs::symlink_dir(&dir, &path)?;
+        }
+
+        Plugin::build(LOCAL_HOST.to_string(), LOCAL_OWNER.to_string(), name, path)
+    }
+
+    /// Install a plugin pinned to an exact commit.
+    ///
+    /// Clones the repository if needed, then checks out `commit`. A fresh clone
+    /// is removed again if the commit cannot be found.
+    pub fn install_pinned(
+        source: GitSource,
+        path: PathBuf,
+        commit: &str,
+        targets: &[LinkTarget],
+    ) -> Result<Plugin, PluginError> {
+        let fresh = !is_git_repo(&path);
+        if fresh {
+            clone_source(&source, &path)?;
+        }
+
+        let plugin = Plugin::build(source.host, source.owner, source.repo, path)?;
+        plugin.checkout(commit, targets).inspect_err(|_| {
+            if fresh {
+                let _ = plugin.remove(targets);
+            }
+        })
+    }
+
+    /// The plugin name (derived from the repository name, scoped to the subdirectory if any).
+    pub fn name(&self) -> Cow<'_, str> {
+        match &self.subdir {
+            Some(subdir) => Cow::Owned(scoped_name(&self.repo, Some(subdir))),
+            None => Cow::Borrowed(&self.repo),
+        }
+    }
+
+    /// Check if this plugin is a local directory registered by reference.
+    pub fn is_local(&self) -> bool {
+        self.host == LOCAL_HOST
+    }
+
+    /// The directory a local plugin refers to, if this is a local plugin.
+    pub fn local_dir(&self) -> Option<PathBuf> {
+        if !self.is_local() {
+            return None;
+        }
+        fs::read_link(&self.path).ok()
     }
 
     /// Get the skills discovered in this plugin.
@@ -138,31 +400,260 @@ impl Plugin {
         &self.skills
     }
 
+    /// Get the bundle components of this plugin, in discovery order.
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    /// Get the bundle components of one kind.
+    pub fn components_of(&self, kind: ComponentKind) -> impl Iterator<Item = &Component> {
+        self.components.iter().filter(move |c| c.kind == kind)
+    }
+
+    /// Get the commit SHA currently checked out, if the plugin is a git repository.
+    pub fn head_commit(&self) -> Option<String> {
+        if self.is_local() || !is_git_repo(&self.path) {
+            return None;
+        }
+        git_head(&self.path).ok()
+    }
+
     /// Set the skills for this plugin.
     pub(crate) fn set_skills(&mut self, skills: Vec<Skill>) {
         self.skills = skills;
     }
 
-    /// Update this plugin by pulling latest changes and rescanning skills.
+    /// Check if updates leave this plugin's checkout alone (pinned to a tag or commit).
+    pub fn is_fixed(&self) -> bool {
+        self.reference.as_ref().is_some_and(GitRef::is_fixed)
+    }
+
+    /// Check if updates only rescan this plugin (local, or pinned to a tag or commit).
+    pub fn is_rescan_only(&self) -> bool {
+        self.is_local() || self.is_fixed()
+    }
+
+    /// Update this plugin by fetching and applying the latest changes, then rescanning skills.
+    /// Returns a new Plugin with refreshed skill list.
+    ///
+    /// Local plugins and plugins pinned to a tag or commit are only rescanned.
+    pub fn update(&self, targets: &[LinkTarget]) -> Result<Plugin, PluginError> {
+        if self.is_rescan_only() {
+            return self.refresh_with(targets, |_| Ok(()));
+        }
+        let pending = self.fetch_update()?;
+        self.apply_update(&pending, targets)
+    }
+
+    /// Fetch upstream changes without applying them.
+    ///
+    /// The returned update lists the incoming commits and the changed files,
+    /// grouped by skill, so they can be reviewed before [`Plugin::apply_update`].
+    pub fn fetch_update(&self) -> Result<PendingUpdate, PluginError> {
+        let from = git_head(&self.path)?;
+        let to = git_fetch(&self.path)?;
+        let commits = parse_log(&git_log(&self.path, &from, &to)?);
+        let pathspec = self.subdir.as_deref().unwrap_or(".");
+        let files = parse_diff(&git_diff(&self.path, &from, &to, pathspec)?);
+
+        // Skills as they are now, plus skills the update adds
+        let root = self.skills_root();
+        let mut skill_dirs: Vec<(String, String)> = self
+            .skills
+            .iter()
+            .filter_map(|s| Some((s.name.clone(), self.relative_dir(&s.path)?)))
+            .collect();
+        for file in files.iter().filter(|f| f.path.ends_with("SKILL.md")) {
+            let path = self.path.join(&file.path);
+            if let Some(dir) = self.relative_dir(&path) {
+                skill_dirs.push((derive_skill_name(&root, &path), dir));
+            }
+        }
+
+        Ok(PendingUpdate {
+            from,
+            to,
+            commits,
+            changes: group_by_skill(files, &skill_dirs),
+        })
+    }
+
+    /// Fetch upstream and count the commits this plugin is behind.
+    ///
+    /// Returns `None` for plugins that updates only rescan, since they never
+    /// move to newer commits.
+    pub fn commits_behind(&self) -> Result<Option<usize>, PluginError> {
+        if self.is_rescan_only() {
+            return Ok(None);
+        }
+        let head = git_head(&self.path)?;
+        let upstream = git_fetch(&self.path)?;
+        Ok(Some(git_count_commits(&self.path, &head, &upstream)?))
+    }
+
+    /// Fast-forward to a fetched update and rescan skills.
+    /// Returns a new Plugin with refreshed skill list.
+    pub fn apply_update(
+        &self,
+        pending: &PendingUpdate,
+        targets: &[LinkTarget],
+    ) -> Result<Plugin, PluginError> {
+        self.refresh_recording_history(targets, |path| git_fast_forward(path, &pending.to))
+    }
+
+    /// The directory containing `file`, relative to the plugin checkout, with `/` separators.
+    fn relative_dir(&self, file: &Path) -> Option<String> {
+        let dir = file.parent()?.strip_prefix(&self.path).ok()?;
+        let parts: Vec<&str> = dir
+            .components()
+            .filter_map(|c| c.as_os_str().to_str())
+            .collect();
+        Some(parts.join("/"))
+    }
+
+    /// Check if this plugin follows `reference`, or the default branch for `None`.
+    ///
+    /// Refs are compared by name, so a `@deadbeef` pin installed as a tag matches.
+    pub fn is_at_ref(&self, reference: Option<&GitRef>) -> bool {
+        self.reference.as_ref().map(GitRef::name) == reference.map(GitRef::name)
+    }
+
+    /// Clone this plugin again from its remote at `reference` (the default
+    /// branch for `None`), replacing the current clone, then rescan skills
+    /// and repair links.
+    /// Returns a new Plugin with refreshed skill list.
+    ///
+    /// The clone is only replaced once the new ref has been checked out.
+    pub fn switch_ref(
+        &self,
+        reference: Option<&GitRef>,
+        targets: &[LinkTarget],
+    ) -> Result<Plugin, PluginError> {
+        let source = GitSource {
+            host: self.host.clone(),
+            owner: self.owner.clone(),
+            repo: self.repo.clone(),
+            url: git_remote_url(&self.path)?,
+            reference: reference.cloned(),
+            subdir: self.subdir.clone(),
+        };
+        self.refresh_recording_history(targets, |path| {
+            let name = dir_name(path).unwrap_or_default();
+            let staging = path.with_file_name(format!("{}{}", name, STAGING_SUFFIX));
+            if staging.exists() {
+                fs::remove_dir_all(&staging)?;
+            }
+            clone_source(&source, &staging)?;
+
+            // Keep the revisions recorded so far
+            if let Some(history) = git_config_get(path, HISTORY_CONFIG_KEY) {
+                git_config_set(&staging, HISTORY_CONFIG_KEY, &history)?;
+            }
+            fs::remove_dir_all(path)?;
+            fs::rename(&staging, path)?;
+            Ok(())
+        })
+    }
+
+    /// Check out an exact commit and rescan skills.
+    /// Returns a new Plugin with refreshed skill list.
+    pub fn checkout(&self, commit: &str, targets: &[LinkTarget]) -> Result<Plugin, PluginError> {
+        self.refresh_recording_history(targets, |path| git_checkout_commit(path, commit))
+    }
+
+    /// Previous revisions of this plugin, most recent first.
+    pub fn history(&self) -> Vec<String> {
+        if self.is_local() || !is_git_repo(&self.path) {
+            return Vec::new();
+        }
+        git_config_get(&self.path, HISTORY_CONFIG_KEY)
+            .map(|value| value.split_whitespace().map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Record the previous revisions of this plugin, keeping the most recent ones.
+    fn set_history(&self, history: &[String]) -> Result<(), PluginError> {
+        let history = &history[..history.len().min(HISTORY_LEN)];
+        git_config_set(&self.path, HISTORY_CONFIG_KEY, &history.join(" "))
+    }
+
+    /// Check out the revision this plugin was at before its last update,
+    /// then rescan skills and repair links.
     /// Returns a new Plugin with refreshed skill list.
-    pub fn update(&self) -> Result<Plugin, PluginError> {
-        if !is_git_repo(&self.path) {
+    pub fn rollback(&self, targets: &[LinkTarget]) -> Result<Plugin, PluginError> {
+        let history = self.history();
+        let Some(previous) = history.first() else {
+            return Err(PluginError::NoPreviousRevision {
+                name: self.name().into_owned(),
+            });
+        };
+
+        let rolled_back = self.refresh_with(targets, |path| git_checkout_commit(path, previous))?;
+        self.set_history(&history[1..])?;
+        Ok(rolled_back)
+    }
+
+    /// Like [`Plugin::refresh_with`], but remember the previous commit if `change` moves HEAD.
+    fn refresh_recording_history(
+        &self,
+        targets: &[LinkTarget],
+        change: impl FnOnce(&Path) -> Result<(), PluginError>,
+    ) -> Result<Plugin, PluginError> {
+        let before = self.head_commit();
+        let updated = self.refresh_with(targets, change)?;
+
+        if let Some(before) = before
+            && updated.head_commit().is_some_and(|after| after != before)
+        {
+            let mut history = self.history();
+            history.insert(0, before);
+            self.set_history(&history)?;
+        }
+        Ok(updated)
+    }
+
+    /// Change the working tree with `change`, then rescan skills, repair
+    /// links in `targets` of skills that were removed or moved, and refresh
+    /// outdated copies.
+    fn refresh_with(
+        &self,
+        targets: &[LinkTarget],
+        change: impl FnOnce(&Path) -> Result<(), PluginError>,
+    ) -> Result<Plugin, PluginError> {
+        let installed = if self.is_local() {
+            self.path.is_dir()
+        } else {
+            is_git_repo(&self.path)
+        };
+        if !installed {
             return Err(PluginError::UpdateFailed {
                 path: self.path.clone(),
                 stderr: "plugin is not installed".to_string(),
             });
         }
 
-        // Collect qualified names and paths of currently linked skills
-        let linked_before: Vec<(String, PathBuf)> = self
+        // Collect currently linked skills, with the targets and modes they are linked with
+        let linked_before: Vec<(&Skill, &LinkTarget, LinkMode)> = self
             .skills
             .iter()
-            .filter(|s| s.is_linked())
-            .map(|s| (s.qualified_name(), s.path.clone()))
+            .flat_map(|s| {
+                targets
+                    .iter()
+                    .filter_map(move |t| Some((s, t, s.link_mode(t)?)))
+            })
+            .collect();
+        let components_before: Vec<(&Component, &LinkTarget)> = self
+            .components
+            .iter()
+            .flat_map(|c| {
+                targets
+                    .iter()
+                    .filter(move |t| c.is_linked_to(t))
+                    .map(move |t| (c, t))
+            })
             .collect();
 
-        // Pull latest changes
-        git_pull(&self.path)?;
+        change(&self.path)?;
 
         // Build new plugin with rescanned skills
         let new_plugin = Plugin::build(
@@ -180,17 +671,22 @@ impl Plugin {
             .collect();
 
         // Handle removed or relocated skills
-        for (name, old_path) in &linked_before {
-            match new_skill_paths.get(name) {
+        for (old_skill, target, mode) in linked_before {
+            let name = old_skill.qualified_name();
+            match new_skill_paths.get(&name) {
                 None => {
-                    // Skill was removed - delete symlink
-                    let _ = skill::remove_skill_symlink(name);
+                    // Skill was removed - delete link
+                    let _ = old_skill.unlink_from(target);
                 }
-                Some(new_path) if new_path != old_path => {
+                Some(new_path) if *new_path != old_skill.path => {
                     // Skill was moved - relink to new location
-                    let _ = skill::remove_skill_symlink(name);
-                    if let Some(skill) = new_plugin.skills.iter().find(|s| &s.qualified_name() == name) {
-                        let _ = skill.link();
+                    let _ = old_skill.unlink_from(target);
+                    if let Some(skill) = new_plugin
+                        .skills
+                        .iter()
+                        .find(|s| s.qualified_name() == name)
+                    {
+                        let _ = skill.link_to_with(target, mode);
                     }
                 }
                 _ => {
@@ -199,12 +695,35 @@ impl Plugin {
             }
         }
 
+        // Components are symlinked files; relink the ones that moved or went away
+        for (old, target) in components_before {
+            let new = new_plugin
+                .components
+                .iter()
+                .find(|c| c.kind == old.kind && c.name == old.name);
+            if new.is_none_or(|c| c.path != old.path) {
+                let _ = old.unlink_from(target);
+                if let Some(component) = new {
+                    let _ = component.link_to(target);
+                }
+            }
+        }
+
+        // Bring copies up to date with the new content
+        for skill in &new_plugin.skills {
+            for target in targets {
+                let _ = skill.refresh_copy(target);
+            }
+        }
+
         Ok(new_plugin)
     }
 
-    /// Remove this plugin from disk and unlink all skills.
-    pub fn remove(&self) -> Result<(), PluginError> {
-        if !self.path.exists() {
+    /// Remove this plugin from disk and unlink all skills from `targets`.
+    ///
+    /// For local plugins only the registration is removed, never the directory itself.
+    pub fn remove(&self, targets: &[LinkTarget]) -> Result<(), PluginError> {
+        if self.path.symlink_metadata().is_err() {
             return Err(PluginError::NotInstalled {
                 name: self.name().to_string(),
             });
@@ -212,10 +731,21 @@ impl Plugin {
 
         // Unlink all skills before removing the plugin directory
         for skill in &self.skills {
-            let _ = skill.unlink(); // Ignore errors (may already be unlinked)
+            for target in targets {
+                let _ = skill.unlink_from(target); // Ignore errors (may already be unlinked)
+            }
+        }
+        for component in &self.components {
+            for target in targets {
+                let _ = component.unlink_from(target);
+            }
         }
 
-        fs::remove_dir_all(&self.path)?;
+        if self.is_local() {
+            fs::remove_file(&self.path)?; // remove_file works on symlinks
+        } else {
+            fs::remove_dir_all(&self.path)?;
+        }
 
         // Clean up empty parent directories
         if let Some(owner_dir) = self.path.parent() {
@@ -233,12 +763,136 @@ impl Plugin {
     }
 }
 
+/// Find an installed skill by its qualified name.
+pub fn find_skill<'a>(
+    plugins: &'a [Arc<Plugin>],
+    qualified_name: &str,
+) -> Result<&'a Skill, PluginError> {
+    plugins
+        .iter()
+        .flat_map(|p| p.skills())
+        .find(|s| s.qualified_name() == qualified_name)
+        .ok_or_else(|| PluginError::SkillNotFound {
+            name: qualified_name.to_string(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plugin::testing::{commit_all, git, init_repo, repo_source, target};
     use std::fs::File;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_install_hex_tag() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("skills");
+        init_repo(&repo);
+        git(&repo, &["tag", "deadbeef"]);
+
+        // `deadbeef` looks like a commit but the remote has it as a tag
+        let source = repo_source(&repo, Some(GitRef::parse_pin("deadbeef")));
+        let plugin = Plugin::install(source, dir.path().join("cache/skills"), &[]).unwrap();
+        assert_eq!(plugin.reference, Some(GitRef::Tag("deadbeef".to_string())));
+        assert_eq!(plugin.skills().len(), 1);
+    }
+
+    #[test]
+    fn test_install_branch_pin() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("skills");
+        init_repo(&repo);
+        git(&repo, &["branch", "stable"]);
+
+        // `@stable` reads as a tag, but the remote only has a branch by that name
+        let source = repo_source(&repo, Some(GitRef::parse_pin("stable")));
+        let plugin = Plugin::install(source, dir.path().join("cache/skills"), &[]).unwrap();
+        assert_eq!(plugin.reference, Some(GitRef::Branch("stable".to_string())));
+        assert!(!plugin.is_fixed());
+    }
+
+    #[test]
+    fn test_commits_behind() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("skills");
+        init_repo(&repo);
+        git(&repo, &["tag", "v1"]);
+
+        let plugin = Plugin::install(
+            repo_source(&repo, None),
+            dir.path().join("cache/skills"),
+            &[],
+        )
+        .unwrap();
+        let tag = Some(GitRef::Tag("v1".to_string()));
+        let pinned = Plugin::install(
+            repo_source(&repo, tag),
+            dir.path().join("cache/pinned"),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(plugin.commits_behind().unwrap(), Some(0));
+
+        commit_all(&repo, "second");
+        commit_all(&repo, "third");
+        assert_eq!(plugin.commits_behind().unwrap(), Some(2));
+        // Checking leaves the checkout alone
+        assert_eq!(plugin.commits_behind().unwrap(), Some(2));
+        // Pinned plugins never move
+        assert_eq!(pinned.commits_behind().unwrap(), None);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("skills");
+        let first = init_repo(&repo);
+
+        let plugin = Plugin::install(
+            repo_source(&repo, None),
+            dir.path().join("cache/skills"),
+            &[],
+        )
+        .unwrap();
+        assert!(matches!(
+            plugin.rollback(&[]),
+            Err(PluginError::NoPreviousRevision { .. })
+        ));
+
+        let second = commit_all(&repo, "second");
+        let updated = plugin.update(&[]).unwrap();
+        assert_eq!(updated.head_commit().as_deref(), Some(second.as_str()));
+        assert_eq!(updated.history(), vec![first.clone()]);
+
+        let rolled_back = updated.rollback(&[]).unwrap();
+        assert_eq!(rolled_back.head_commit().as_deref(), Some(first.as_str()));
+        assert!(rolled_back.history().is_empty());
+        assert_eq!(rolled_back.skills().len(), 1);
+    }
+
+    #[test]
+    fn test_install_other_ref() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("skills");
+        init_repo(&repo);
+        git(&repo, &["tag", "v1"]);
+        commit_all(&repo, "second");
+        git(&repo, &["tag", "v2"]);
+
+        let path = dir.path().join("cache/skills");
+        let v1 = Some(GitRef::Tag("v1".to_string()));
+        Plugin::install(repo_source(&repo, v1.clone()), path.clone(), &[]).unwrap();
+
+        // The same ref again is fine, another one is not silently ignored
+        assert!(Plugin::install(repo_source(&repo, v1), path.clone(), &[]).is_ok());
+        let v2 = Some(GitRef::Tag("v2".to_string()));
+        assert!(matches!(
+            Plugin::install(repo_source(&repo, v2), path, &[]),
+            Err(PluginError::RefMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_plugin_name() {
         let dir = tempdir().unwrap();
@@ -269,6 +923,39 @@ mod tests {
         assert!(plugin.skills().is_empty());
     }
 
+    #[test]
+    fn test_local_plugin() {
+        let cache = tempdir().unwrap();
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join("foo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        File::create(skill_dir.join("SKILL.md")).unwrap();
+
+        let path = cache.path().join("local/local/skills");
+        let plugin = Plugin::install_local(dir.path().to_path_buf(), path.clone()).unwrap();
+        let targets = [target(&cache.path().join("target"))];
+        plugin.skills()[0].link_to(&targets[0]).unwrap();
+        assert!(plugin.is_local());
+        assert_eq!(plugin.local_dir().as_deref(), Some(dir.path()));
+        assert_eq!(plugin.skills().len(), 1);
+        assert!(plugin.head_commit().is_none());
+
+        // New skills show up on update without touching git
+        let bar_dir = dir.path().join("bar");
+        fs::create_dir_all(&bar_dir).unwrap();
+        File::create(bar_dir.join("SKILL.md")).unwrap();
+        let updated = plugin.update(&targets).unwrap();
+        assert_eq!(updated.skills().len(), 2);
+        let foo = updated.skills().iter().find(|s| s.name == "foo").unwrap();
+        assert!(foo.is_linked_to(&targets[0]));
+
+        // Removing the plugin unlinks its skills but leaves the directory alone
+        updated.remove(&targets).unwrap();
+        assert!(!foo.is_linked_to(&targets[0]));
+        assert!(path.symlink_metadata().is_err());
+        assert!(skill_dir.join("SKILL.md").exists());
+    }
+
     #[test]
     fn test_scan_for_skills_empty() {
         let dir = tempdir().unwrap();
@@ -285,11 +972,7 @@ mod tests {
 
         assert_eq!(skills.len(), 1);
         // The skill name should be the temp directory name
-        let expected_name = dir
-            .path()
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap();
+        let expected_name = dir.path().file_name().and_then(|n| n.to_str()).unwrap();
         assert_eq!(skills[0].0, expected_name);
     }
 
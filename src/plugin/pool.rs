@@ -0,0 +1,61 @@
+//! A minimal worker pool for running plugin operations in parallel.
+
+use std::sync::Mutex;
+use std::thread;
+
+/// Run `work` on every item, with at most `limit` items in flight at once.
+///
+/// Blocks until all items are done. A `limit` of zero is treated as one.
+pub(crate) fn run<T: Send>(items: Vec<T>, limit: usize, work: impl Fn(T) + Sync) {
+    let workers = limit.max(1).min(items.len());
+    let queue = Mutex::new(items.into_iter());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    // Release the lock before working on the item
+                    let item = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                    match item {
+                        Some(item) => work(item),
+                        None => break,
+                    }
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_respects_limit() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+
+        run((0..12).collect(), 3, |_: i32| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(done.load(Ordering::SeqCst), 12);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn test_run_zero_limit() {
+        let done = AtomicUsize::new(0);
+        run(vec![1, 2], 0, |_: i32| {
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,419 @@
+//! A full-text index of skills, for finding them by topic.
+//!
+//! Installed skills are indexed by name, description and the body of
+//! their SKILL.md. Plugins offered by marketplaces but not installed are
+//! indexed by their catalog entry, and by their skills when the plugin
+//! lives inside the marketplace and its files are at hand.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::marketplace::{Marketplace, MarketplaceEntry};
+use super::metadata::{SkillMetadata, split_frontmatter};
+use super::plugin::{Plugin, scan_for_skills};
+
+/// BM25 term frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 length normalization.
+const B: f64 = 0.75;
+/// How much a match in each field counts: name, description, body.
+const FIELD_WEIGHTS: [f64; 3] = [3.0, 2.0, 1.0];
+/// How much a term counts when it only prefixes a word, e.g. `format` in `formatting`.
+const PREFIX_WEIGHT: f64 = 0.5;
+/// Longest snippet of a matching body line, in characters.
+const SNIPPET_LEN: usize = 100;
+
+/// Where a searchable document comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchSource {
+    /// A skill of an installed plugin.
+    Installed {
+        /// The plugin, as `owner/repo`.
+        plugin: String,
+        qualified_name: String,
+    },
+    /// A plugin listed in a marketplace and not installed, or one of its skills.
+    Available { marketplace: String, entry: String },
+}
+
+/// A skill or marketplace entry in the index.
+#[derive(Debug, Clone)]
+pub struct SearchDoc {
+    pub source: SearchSource,
+    /// The skill name, or the entry name for the entry itself.
+    pub name: String,
+    pub description: Option<String>,
+    /// The markdown after the frontmatter, if any.
+    body: String,
+}
+
+impl SearchDoc {
+    /// A name to show for this document: the qualified name of an installed
+    /// skill, or `entry@marketplace` with the skill name for available ones.
+    pub fn label(&self) -> String {
+        match &self.source {
+            SearchSource::Installed { qualified_name, .. } => qualified_name.clone(),
+            SearchSource::Available { marketplace, entry } if *entry == self.name => {
+                format!("{}@{}", entry, marketplace)
+            }
+            SearchSource::Available { marketplace, entry } => {
+                format!("{}@{}: {}", entry, marketplace, self.name)
+            }
+        }
+    }
+}
+
+/// A document matching a query.
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    /// Index of the document, for [`SearchIndex::doc`].
+    pub doc: usize,
+    pub score: f64,
+    /// The first body line mentioning a query term, if the body matched.
+    pub snippet: Option<String>,
+}
+
+/// Term frequencies and length of one field of a document.
+#[derive(Debug, Default)]
+struct Field {
+    terms: HashMap<String, u32>,
+    len: usize,
+}
+
+/// An in-memory inverted index over skill names, descriptions and bodies.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    /// Per document: name, description and body.
+    fields: Vec<[Field; 3]>,
+    /// Documents containing each term, sorted so prefixes can be scanned.
+    postings: BTreeMap<String, Vec<usize>>,
+    /// Average length of each field.
+    avg_len: [f64; 3],
+}
+
+/// Split text into lowercase alphanumeric words.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Read a SKILL.md file into its metadata and the body after the frontmatter.
+fn read_skill(path: &Path) -> Option<(SkillMetadata, String)> {
+    let content = fs::read_to_string(path).ok()?;
+    let metadata = SkillMetadata::parse(&content).unwrap_or_default();
+    let body = match split_frontmatter(&content) {
+        // The closing `---` follows the YAML; the first YAML line is 1-based
+        Some(Ok((yaml, first))) => content
+            .lines()
+            .skip(first + yaml.lines().count())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => content,
+    };
+    Some((metadata, body))
+}
+
+impl SearchIndex {
+    /// Index installed skills, and the marketplace entries for which
+    /// `is_installed` is false.
+    pub fn build<F>(
+        plugins: &[Arc<Plugin>],
+        marketplaces: &[Marketplace],
+        is_installed: F,
+    ) -> SearchIndex
+    where
+        F: Fn(&Marketplace, &MarketplaceEntry) -> bool,
+    {
+        let mut index = SearchIndex::default();
+        for plugin in plugins {
+            for skill in plugin.skills() {
+                let body = read_skill(&skill.path)
+                    .map(|(_, body)| body)
+                    .unwrap_or_default();
+                index.add(SearchDoc {
+                    source: SearchSource::Installed {
+                        plugin: format!("{}/{}", plugin.owner, plugin.name()),
+                        qualified_name: skill.qualified_name(),
+                    },
+                    name: skill.name.clone(),
+                    description: skill.description().map(String::from),
+                    body,
+                });
+            }
+        }
+
+        for marketplace in marketplaces {
+            for entry in marketplace
+                .plugins
+                .iter()
+                .filter(|e| !is_installed(marketplace, e))
+            {
+                let source = SearchSource::Available {
+                    marketplace: marketplace.name.clone(),
+                    entry: entry.name.clone(),
+                };
+                index.add(SearchDoc {
+                    source: source.clone(),
+                    name: entry.name.clone(),
+                    description: entry.description.clone(),
+                    body: String::new(),
+                });
+
+                let skills = marketplace
+                    .entry_dir(entry)
+                    .and_then(|dir| scan_for_skills(&dir).ok());
+                for (name, path) in skills.into_iter().flatten() {
+                    let Some((metadata, body)) = read_skill(&path) else {
+                        continue;
+                    };
+                    index.add(SearchDoc {
+                        source: source.clone(),
+                        name,
+                        description: metadata
+                            .description
+                            .map(|d| d.trim().to_string())
+                            .filter(|d| !d.is_empty()),
+                        body,
+                    });
+                }
+            }
+        }
+
+        let count = index.docs.len().max(1) as f64;
+        for (i, avg) in index.avg_len.iter_mut().enumerate() {
+            *avg = index.fields.iter().map(|f| f[i].len).sum::<usize>() as f64 / count;
+        }
+        index
+    }
+
+    /// Add a document to the index. Averages are computed by [`SearchIndex::build`].
+    fn add(&mut self, doc: SearchDoc) {
+        let id = self.docs.len();
+        let texts = [
+            doc.name.as_str(),
+            doc.description.as_deref().unwrap_or_default(),
+            doc.body.as_str(),
+        ];
+        let fields = texts.map(|text| {
+            let mut field = Field::default();
+            for term in tokenize(text) {
+                *field.terms.entry(term).or_default() += 1;
+                field.len += 1;
+            }
+            field
+        });
+
+        let terms: HashSet<&String> = fields.iter().flat_map(|f| f.terms.keys()).collect();
+        for term in terms {
+            self.postings.entry(term.clone()).or_default().push(id);
+        }
+        self.docs.push(doc);
+        self.fields.push(fields);
+    }
+
+    /// Get the number of indexed documents.
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Check if nothing was indexed.
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Get a document by index.
+    pub fn doc(&self, id: usize) -> &SearchDoc {
+        &self.docs[id]
+    }
+
+    /// Score one indexed term for a document with BM25, summed over its fields.
+    fn score_term(&self, id: usize, term: &str, docs_with_term: usize) -> f64 {
+        let n = self.docs.len() as f64;
+        let df = docs_with_term as f64;
+        let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+        self.fields[id]
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let Some(&tf) = field.terms.get(term) else {
+                    return 0.0;
+                };
+                let tf = f64::from(tf);
+                let norm = 1.0 - B + B * field.len as f64 / self.avg_len[i].max(1.0);
+                FIELD_WEIGHTS[i] * tf * (K1 + 1.0) / (tf + K1 * norm)
+            })
+            .sum::<f64>()
+            * idf
+    }
+
+    /// Find the documents matching every word of `query`, best first.
+    ///
+    /// A word matches whole words, or, at a lower score, words it is a prefix of.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let words: Vec<String> = tokenize(query).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for (i, word) in words.iter().enumerate() {
+            // The best score of this word in each document
+            let mut best: HashMap<usize, f64> = HashMap::new();
+            for (term, docs) in self
+                .postings
+                .range(word.clone()..)
+                .take_while(|(t, _)| t.starts_with(word.as_str()))
+            {
+                let weight = if term == word { 1.0 } else { PREFIX_WEIGHT };
+                for &id in docs {
+                    let score = weight * self.score_term(id, term, docs.len());
+                    let entry = best.entry(id).or_default();
+                    *entry = entry.max(score);
+                }
+            }
+
+            // Every word must match
+            if i == 0 {
+                scores = best;
+            } else {
+                scores.retain(|id, score| match best.get(id) {
+                    Some(s) => {
+                        *score += s;
+                        true
+                    }
+                    None => false,
+                });
+            }
+        }
+
+        let mut results: Vec<SearchResult> = scores
+            .into_iter()
+            .map(|(doc, score)| SearchResult {
+                doc,
+                score,
+                snippet: self.snippet(doc, &words),
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| self.docs[a.doc].label().cmp(&self.docs[b.doc].label()))
+        });
+        results
+    }
+
+    /// The first body line of a document containing one of `words`.
+    fn snippet(&self, id: usize, words: &[String]) -> Option<String> {
+        let line = self.docs[id].body.lines().map(str::trim).find(|line| {
+            let line = line.to_lowercase();
+            words.iter().any(|w| line.contains(w.as_str()))
+        })?;
+        Some(match line.char_indices().nth(SNIPPET_LEN) {
+            Some((end, _)) => format!("{}...", &line[..end]),
+            None => line.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::MARKETPLACE_MANIFEST;
+    use crate::plugin::marketplace::MarketplaceSource;
+    use tempfile::tempdir;
+
+    fn write_skill(dir: &Path, name: &str, description: &str, body: &str) {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        let content = format!(
+            "---\nname: {}\ndescription: {}\n---\n{}",
+            name, description, body
+        );
+        fs::write(dir.join(name).join("SKILL.md"), content).unwrap();
+    }
+
+    #[test]
+    fn test_search() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("me").join("tools");
+        write_skill(
+            &repo,
+            "pdf",
+            "Fill and extract PDF forms",
+            "Use pdftk to fill forms.",
+        );
+        write_skill(
+            &repo,
+            "xlsx",
+            "Edit spreadsheets",
+            "Charts and formulas.\nExport tables to PDF when asked.",
+        );
+        write_skill(
+            &repo,
+            "notes",
+            "Take meeting notes",
+            "Summarize the meeting.",
+        );
+        let plugin = Plugin::build(
+            "github.com".to_string(),
+            "me".to_string(),
+            "tools".to_string(),
+            repo,
+        )
+        .unwrap();
+
+        let catalog = dir.path().join("catalog");
+        fs::create_dir_all(catalog.join(".claude-plugin")).unwrap();
+        fs::write(
+            catalog.join(MARKETPLACE_MANIFEST),
+            r#"{"name": "team", "plugins": [
+                {"name": "docs", "source": "./docs", "description": "Document tools"},
+                {"name": "tools", "source": {"source": "github", "repo": "me/tools"}}
+            ]}"#,
+        )
+        .unwrap();
+        write_skill(
+            &catalog.join("docs"),
+            "scan",
+            "Scan paper",
+            "OCR scanned PDF pages.",
+        );
+        let marketplace =
+            Marketplace::read(&catalog, MarketplaceSource::Local(catalog.clone())).unwrap();
+
+        let index = SearchIndex::build(&[Arc::new(plugin)], &[marketplace], |_, e| {
+            e.name == "tools"
+        });
+        // Three skills, the docs entry and its skill; the installed entry is left out
+        assert_eq!(index.len(), 5);
+
+        let labels = |query: &str| -> Vec<String> {
+            index
+                .search(query)
+                .iter()
+                .map(|r| index.doc(r.doc).label())
+                .collect()
+        };
+        // Descriptions rank above bodies
+        assert_eq!(
+            labels("pdf"),
+            vec!["me:tools:pdf", "docs@team: scan", "me:tools:xlsx"]
+        );
+        // Every word must match
+        assert_eq!(labels("pdf forms"), vec!["me:tools:pdf"]);
+        // Words match prefixes too
+        assert_eq!(labels("spread"), vec!["me:tools:xlsx"]);
+        assert_eq!(labels("document"), vec!["docs@team"]);
+        assert!(labels("").is_empty());
+
+        let results = index.search("tables");
+        assert_eq!(
+            results[0].snippet.as_deref(),
+            Some("Export tables to PDF when asked.")
+        );
+    }
+}
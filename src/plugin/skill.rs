@@ -1,41 +1,43 @@
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
 
+use super::copy::{self, CopyRecord};
 use super::error::PluginError;
+use super::lint::{Diagnostic, Severity, lint_skill_file};
+use super::metadata::{FrontmatterError, SkillMetadata};
+use super::target::{LinkMode, LinkTarget, Naming};
 
-/// Target for skill linking.
+/// The state of a skill's link in one target.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum LinkTarget {
-    ClaudeCode,
-    Codex,
+pub enum LinkStatus {
+    NotLinked,
+    /// Symlinked to the skill.
+    Symlinked,
+    /// Copied, and the copy matches the skill.
+    Copied,
+    /// Copied, but the skill has changed since.
+    Outdated,
+    /// Copied, and the copy was edited in place.
+    Modified,
 }
 
-impl LinkTarget {
-    /// Get the skills directory for this target.
-    pub fn skills_dir(&self) -> Option<PathBuf> {
-        dirs::home_dir().map(|h| match self {
-            LinkTarget::ClaudeCode => h.join(".claude").join("skills"),
-            LinkTarget::Codex => h.join(".codex").join("skills"),
-        })
+impl LinkStatus {
+    /// Check if the skill is present in the target, up to date or not.
+    pub fn is_linked(&self) -> bool {
+        *self != LinkStatus::NotLinked
     }
 
-    /// Get the display name for this target.
-    pub fn display_name(&self) -> &'static str {
+    /// Get the stable identifier for this status.
+    pub fn id(&self) -> &'static str {
         match self {
-            LinkTarget::ClaudeCode => "Claude Code",
-            LinkTarget::Codex => "Codex",
+            LinkStatus::NotLinked => "not-linked",
+            LinkStatus::Symlinked => "symlinked",
+            LinkStatus::Copied => "copied",
+            LinkStatus::Outdated => "outdated",
+            LinkStatus::Modified => "modified",
         }
     }
-
-    /// Get all available link targets.
-    pub fn all() -> &'static [LinkTarget] {
-        &[LinkTarget::ClaudeCode, LinkTarget::Codex]
-    }
-}
-
-/// Get the Claude Code skills directory (~/.claude/skills).
-fn claude_skills_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("skills"))
 }
 
 /// Check if a symlink exists at the given path (even if broken).
@@ -43,56 +45,39 @@ fn symlink_exists(path: &Path) -> bool {
     path.symlink_metadata().is_ok()
 }
 
-/// Remove a symlink from the Claude skills directory by qualified name.
-/// Works even when the symlink target no longer exists (broken symlink).
-pub fn remove_skill_symlink(qualified_name: &str) -> Result<(), PluginError> {
-    let skills_dir = claude_skills_dir().ok_or(PluginError::LinkFailed {
-        name: qualified_name.to_string(),
-        reason: "cannot determine home directory".to_string(),
-    })?;
-
-    let link_path = skills_dir.join(qualified_name);
-
-    if symlink_exists(&link_path) {
-        fs::remove_file(&link_path)?;
+/// Get the path of `to` relative to the directory `from`.
+///
+/// Both paths must be absolute; resolve symlinks first so `..` steps land
+/// where they should.
+pub(crate) fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
     }
-
-    Ok(())
+    for component in &to[common..] {
+        path.push(component);
+    }
+    path
 }
 
-/// Parse the description from YAML frontmatter in a SKILL.md file.
-fn parse_description(path: &Path) -> Option<String> {
-    let content = fs::read_to_string(path).ok()?;
-    let content = content.trim_start();
-
-    // Check for YAML frontmatter delimiter
-    if !content.starts_with("---") {
-        return None;
-    }
-
-    // Find the closing delimiter
-    let rest = &content[3..];
-    let end = rest.find("---")?;
-    let frontmatter = &rest[..end];
-
-    // Look for description field
-    for line in frontmatter.lines() {
-        let line = line.trim();
-        if let Some(value) = line.strip_prefix("description:") {
-            let value = value.trim();
-            // Handle quoted strings
-            let value = value
-                .strip_prefix('"')
-                .and_then(|s| s.strip_suffix('"'))
-                .or_else(|| value.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
-                .unwrap_or(value);
-            if !value.is_empty() {
-                return Some(value.to_string());
+/// Resolve `.` and `..` in a path without touching the filesystem, for paths
+/// that may no longer exist.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
             }
+            Component::CurDir => {}
+            component => normalized.push(component),
         }
     }
-
-    None
+    normalized
 }
 
 /// A skill discovered within a plugin.
@@ -102,27 +87,84 @@ pub struct Skill {
     pub name: String,
     /// The path to the SKILL.md file.
     pub path: PathBuf,
-    /// The description from SKILL.md frontmatter.
-    pub description: Option<String>,
+    /// Metadata from SKILL.md frontmatter (empty if it could not be parsed).
+    pub metadata: SkillMetadata,
+    /// Why the frontmatter could not be parsed, if it could not.
+    pub frontmatter_error: Option<FrontmatterError>,
+    /// Problems found when validating SKILL.md, once checked.
+    diagnostics: OnceLock<Vec<Diagnostic>>,
     /// The owner (username/org) of the parent plugin.
     owner: String,
     /// The repository name of the parent plugin.
     repo: String,
+    /// The directory of the parent plugin.
+    plugin_dir: PathBuf,
 }
 
 impl Skill {
-    /// Create a new skill with owner and repo information from its parent plugin.
-    pub(crate) fn new(name: String, path: PathBuf, owner: String, repo: String) -> Self {
-        let description = parse_description(&path);
+    /// Create a new skill with owner, repo and directory information from its parent plugin.
+    pub(crate) fn new(
+        name: String,
+        path: PathBuf,
+        owner: String,
+        repo: String,
+        plugin_dir: PathBuf,
+    ) -> Self {
+        let (metadata, frontmatter_error) = match SkillMetadata::read(&path) {
+            Ok(metadata) => (metadata, None),
+            Err(e) => (SkillMetadata::default(), Some(e)),
+        };
         Self {
             name,
             path,
-            description,
+            metadata,
+            frontmatter_error,
+            diagnostics: OnceLock::new(),
             owner,
             repo,
+            plugin_dir,
         }
     }
 
+    /// Problems found when validating SKILL.md.
+    ///
+    /// The file is only checked when first asked, not on every plugin scan.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics
+            .get_or_init(|| lint_skill_file(&self.name, &self.path))
+    }
+
+    /// Count the diagnostics of a given severity.
+    pub fn diagnostic_count(&self, severity: Severity) -> usize {
+        self.diagnostics()
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+
+    /// The description from SKILL.md frontmatter, without surrounding whitespace.
+    pub fn description(&self) -> Option<&str> {
+        self.metadata
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+    }
+
+    /// The directory containing SKILL.md.
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or(&self.path)
+    }
+
+    /// List the other files in the skill directory, relative to it and sorted.
+    ///
+    /// These are what the agent gets access to alongside SKILL.md.
+    pub fn files(&self) -> Result<Vec<PathBuf>, PluginError> {
+        let mut files = copy::list_files(self.dir())?;
+        files.retain(|f| f != Path::new("SKILL.md"));
+        Ok(files)
+    }
+
     /// Get the qualified name for this skill (owner:repo:skillname).
     ///
     /// This format ensures unique symlink names across different plugins,
@@ -132,39 +174,110 @@ impl Skill {
     }
 
     /// Get the link path for this skill for a specific target.
-    pub fn link_path_for(&self, target: LinkTarget) -> Option<PathBuf> {
-        let skills_dir = target.skills_dir()?;
-        Some(skills_dir.join(self.qualified_name()))
+    pub fn link_path_for(&self, target: &LinkTarget) -> PathBuf {
+        let name = match target.naming {
+            Naming::Qualified => self.qualified_name(),
+            Naming::Plain => self.name.clone(),
+        };
+        target.dir.join(name)
     }
 
-    /// Get the link path for this skill (Claude Code).
+    /// Check if this skill is linked to a specific target.
     ///
-    /// Uses the qualified name (owner:repo:skillname) to avoid collisions.
-    pub fn link_path(&self) -> Option<PathBuf> {
-        self.link_path_for(LinkTarget::ClaudeCode)
+    /// Symlinks must resolve, so broken symlinks count as "not linked". Copies
+    /// count as linked even when out of date; see [`Skill::link_status`]. A
+    /// link to another skill of the same name counts as "not linked".
+    pub fn is_linked_to(&self, target: &LinkTarget) -> bool {
+        self.link_mode(target).is_some()
     }
 
-    /// Check if this skill is linked to a specific target.
+    /// How this skill is linked to a target, without checking copies for changes.
+    pub fn link_mode(&self, target: &LinkTarget) -> Option<LinkMode> {
+        let link_path = self.link_path_for(target);
+        if let Some(record) = CopyRecord::read(&link_path) {
+            (record.skill == self.qualified_name()).then_some(LinkMode::Copy)
+        } else if link_path.is_symlink() && self.is_dir(&link_path) {
+            Some(LinkMode::Symlink)
+        } else {
+            None
+        }
+    }
+
+    /// Check if `path` is or resolves to this skill's directory.
+    fn is_dir(&self, path: &Path) -> bool {
+        path == self.dir()
+            || path
+                .canonicalize()
+                .is_ok_and(|p| self.dir().canonicalize().is_ok_and(|dir| p == dir))
+    }
+
+    /// Get the state of this skill's link in a target.
     ///
-    /// Uses `exists()` which follows the symlink and checks if the target exists,
-    /// correctly detecting broken symlinks as "not linked".
-    pub fn is_linked_to(&self, target: LinkTarget) -> bool {
-        self.link_path_for(target)
-            .map(|p| p.exists())
-            .unwrap_or(false)
+    /// Copies are hashed and compared with the hash recorded when they were
+    /// made, to tell if the copy was edited or the skill has changed since.
+    /// Hashes are reused until a file changes, so this is cheap to call on
+    /// every frame.
+    pub fn link_status(&self, target: &LinkTarget) -> LinkStatus {
+        let link_path = self.link_path_for(target);
+        match self.link_mode(target) {
+            None => return LinkStatus::NotLinked,
+            Some(LinkMode::Symlink) => return LinkStatus::Symlinked,
+            Some(LinkMode::Copy) => {}
+        }
+        let Some(record) = CopyRecord::read(&link_path) else {
+            return LinkStatus::NotLinked;
+        };
+
+        if copy::hash_dir_cached(&link_path).ok().as_ref() != Some(&record.hash) {
+            LinkStatus::Modified
+        } else if copy::hash_dir_cached(self.dir()).ok().as_ref() != Some(&record.hash) {
+            LinkStatus::Outdated
+        } else {
+            LinkStatus::Copied
+        }
+    }
+
+    /// Check if this skill's link in a target is a symlink that no longer resolves.
+    pub fn is_link_broken(&self, target: &LinkTarget) -> bool {
+        let link_path = self.link_path_for(target);
+        link_path.is_symlink() && !link_path.exists()
     }
 
-    /// Check if this skill is linked to Claude Code.
+    /// Check if this skill's link in a target is a broken symlink into its own
+    /// plugin, rather than another plugin's link of the same name.
+    fn is_own_link_broken(&self, target: &LinkTarget) -> bool {
+        let link_path = self.link_path_for(target);
+        let (Some(parent), Ok(dest)) = (link_path.parent(), fs::read_link(&link_path)) else {
+            return false;
+        };
+        if link_path.exists() {
+            return false;
+        }
+        // Relative links were written against the resolved skills directory
+        let parent = parent
+            .canonicalize()
+            .unwrap_or_else(|_| parent.to_path_buf());
+        let dest = normalize(&parent.join(dest));
+        dest.starts_with(&self.plugin_dir)
+            || self
+                .plugin_dir
+                .canonicalize()
+                .is_ok_and(|dir| dest.starts_with(dir))
+    }
+
+    /// Check if this skill is linked to any configured target.
     pub fn is_linked(&self) -> bool {
-        self.is_linked_to(LinkTarget::ClaudeCode)
+        LinkTarget::all().iter().any(|t| self.is_linked_to(t))
+    }
+
+    /// Link this skill to a specific target's skills directory, using the target's link mode.
+    pub fn link_to(&self, target: &LinkTarget) -> Result<(), PluginError> {
+        self.link_to_with(target, target.mode)
     }
 
     /// Link this skill to a specific target's skills directory.
-    pub fn link_to(&self, target: LinkTarget) -> Result<(), PluginError> {
-        let link_path = self.link_path_for(target).ok_or(PluginError::LinkFailed {
-            name: self.name.clone(),
-            reason: "cannot determine home directory".to_string(),
-        })?;
+    pub fn link_to_with(&self, target: &LinkTarget, mode: LinkMode) -> Result<(), PluginError> {
+        let link_path = self.link_path_for(target);
 
         if symlink_exists(&link_path) {
             return Err(PluginError::AlreadyLinked {
@@ -183,39 +296,197 @@ impl Skill {
             reason: "invalid skill path".to_string(),
         })?;
 
-        // Create symlink
-        #[cfg(unix)]
-        std::os::unix::fs::symlink(skill_dir, &link_path)?;
-
-        #[cfg(windows)]
-        std::os::windows::fs::symlink_dir(skill_dir, &link_path)?;
+        match mode {
+            LinkMode::Copy => copy::copy_dir(skill_dir, &link_path, &self.qualified_name())?,
+            LinkMode::Symlink => {
+                let skill_dir = match link_path.parent() {
+                    Some(parent) if target.relative => {
+                        relative_path(&parent.canonicalize()?, &skill_dir.canonicalize()?)
+                    }
+                    _ => skill_dir.to_path_buf(),
+                };
+
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(&skill_dir, &link_path)?;
+
+                #[cfg(windows)]
+                std::os::windows::fs::symlink_dir(&skill_dir, &link_path)?;
+            }
+        }
 
         Ok(())
     }
 
-    /// Link this skill to Claude Code's skills directory.
-    pub fn link(&self) -> Result<(), PluginError> {
-        self.link_to(LinkTarget::ClaudeCode)
+    /// Copy this skill into a target again if its copy there is out of date.
+    ///
+    /// Copies that were edited in place are left alone. Returns whether the
+    /// copy was refreshed.
+    pub fn refresh_copy(&self, target: &LinkTarget) -> Result<bool, PluginError> {
+        if self.link_status(target) != LinkStatus::Outdated {
+            return Ok(false);
+        }
+        copy::copy_dir(
+            self.dir(),
+            &self.link_path_for(target),
+            &self.qualified_name(),
+        )?;
+        Ok(true)
     }
 
     /// Unlink this skill from a specific target's skills directory.
-    pub fn unlink_from(&self, target: LinkTarget) -> Result<(), PluginError> {
-        let link_path = self.link_path_for(target).ok_or(PluginError::NotLinked {
-            name: self.name.clone(),
-        })?;
+    ///
+    /// Broken symlinks into this skill's plugin are removed too, but a link to
+    /// another skill of the same name is left alone, broken or not.
+    pub fn unlink_from(&self, target: &LinkTarget) -> Result<(), PluginError> {
+        let link_path = self.link_path_for(target);
 
-        if !symlink_exists(&link_path) {
+        if !self.is_linked_to(target) && !self.is_own_link_broken(target) {
             return Err(PluginError::NotLinked {
                 name: self.name.clone(),
             });
         }
 
-        fs::remove_file(&link_path)?; // remove_file works on symlinks
+        if CopyRecord::read(&link_path).is_some() {
+            copy::remove_copy(&link_path)?;
+        } else {
+            fs::remove_file(&link_path)?; // remove_file works on symlinks
+        }
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path(Path::new("/a/b/c"), Path::new("/a/d")),
+            PathBuf::from("../../d")
+        );
+        assert_eq!(
+            relative_path(Path::new("/a"), Path::new("/a/b")),
+            PathBuf::from("b")
+        );
+    }
+
+    #[test]
+    fn test_relative_symlink() {
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join("plugin").join("pdf");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# PDF").unwrap();
+        let skill = Skill::new(
+            "pdf".to_string(),
+            skill_dir.join("SKILL.md"),
+            "me".to_string(),
+            "tools".to_string(),
+            dir.path().join("plugin"),
+        );
+
+        let mut target = LinkTarget::project(dir.path()).remove(0);
+        target.mode = LinkMode::Symlink;
+        target.relative = true;
+        skill.link_to(&target).unwrap();
+        let link_path = skill.link_path_for(&target);
+        assert_eq!(
+            fs::read_link(&link_path).unwrap(),
+            PathBuf::from("../../plugin/pdf")
+        );
+        assert_eq!(skill.link_status(&target), LinkStatus::Symlinked);
+
+        skill.unlink_from(&target).unwrap();
+        target.relative = false;
+        skill.link_to(&target).unwrap();
+        assert!(fs::read_link(&link_path).unwrap().is_absolute());
+
+        // A link left behind by a moved skill is broken, not linked
+        fs::rename(&skill_dir, dir.path().join("pdf-moved")).unwrap();
+        assert!(skill.is_link_broken(&target));
+        assert_eq!(skill.link_status(&target), LinkStatus::NotLinked);
+        skill.unlink_from(&target).unwrap();
+
+        // Broken relative links into the plugin are removed too
+        fs::rename(dir.path().join("pdf-moved"), &skill_dir).unwrap();
+        target.relative = true;
+        skill.link_to(&target).unwrap();
+        fs::rename(&skill_dir, dir.path().join("pdf-moved")).unwrap();
+        skill.unlink_from(&target).unwrap();
+        assert!(!link_path.is_symlink());
+    }
+
+    #[test]
+    fn test_copy_from_other_cache() {
+        let dir = tempdir().unwrap();
+        let skill = |cache: &str| {
+            let skill_dir = dir.path().join(cache).join("pdf");
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(skill_dir.join("SKILL.md"), "# PDF").unwrap();
+            Skill::new(
+                "pdf".to_string(),
+                skill_dir.join("SKILL.md"),
+                "me".to_string(),
+                "tools".to_string(),
+                dir.path().join(cache),
+            )
+        };
+        let (author, teammate) = (skill("author"), skill("teammate"));
+
+        // A copy committed by the author belongs to the same skill in a teammate's cache
+        let target = LinkTarget::project(dir.path()).remove(0);
+        author.link_to(&target).unwrap();
+        assert_eq!(teammate.link_status(&target), LinkStatus::Copied);
+        assert!(matches!(
+            teammate.link_to(&target),
+            Err(PluginError::AlreadyLinked { .. })
+        ));
+        teammate.unlink_from(&target).unwrap();
+        assert!(!author.is_linked_to(&target));
+    }
+
+    #[test]
+    fn test_plain_names_shared() {
+        let dir = tempdir().unwrap();
+        let skill = |repo: &str| {
+            let skill_dir = dir.path().join(repo).join("pdf");
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(skill_dir.join("SKILL.md"), "# PDF").unwrap();
+            Skill::new(
+                "pdf".to_string(),
+                skill_dir.join("SKILL.md"),
+                "me".to_string(),
+                repo.to_string(),
+                dir.path().join(repo),
+            )
+        };
+        let (ours, theirs) = (skill("tools"), skill("docs"));
+
+        let mut target = LinkTarget::project(dir.path()).remove(0);
+        target.naming = Naming::Plain;
+        for mode in [LinkMode::Symlink, LinkMode::Copy] {
+            target.mode = mode;
+            ours.link_to(&target).unwrap();
+            assert_eq!(ours.link_mode(&target), Some(mode));
+
+            // The other plugin's skill of the same name neither claims nor removes the link
+            assert!(!theirs.is_linked_to(&target));
+            assert_eq!(theirs.link_status(&target), LinkStatus::NotLinked);
+            assert!(theirs.unlink_from(&target).is_err());
+            assert!(ours.is_linked_to(&target));
+
+            ours.unlink_from(&target).unwrap();
+        }
 
-    /// Unlink this skill from Claude Code's skills directory.
-    pub fn unlink(&self) -> Result<(), PluginError> {
-        self.unlink_from(LinkTarget::ClaudeCode)
+        // A broken link is only removed by the plugin it pointed into
+        target.mode = LinkMode::Symlink;
+        theirs.link_to(&target).unwrap();
+        fs::remove_dir_all(theirs.dir()).unwrap();
+        assert!(ours.is_link_broken(&target));
+        assert!(ours.unlink_from(&target).is_err());
+        assert!(ours.link_path_for(&target).is_symlink());
+        theirs.unlink_from(&target).unwrap();
+        assert!(!ours.link_path_for(&target).is_symlink());
     }
 }
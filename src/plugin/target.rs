@@ -0,0 +1,167 @@
+//! Link targets: the directories skills are linked into.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::config::Settings;
+use super::git::git_toplevel;
+
+/// How a skill is placed into a target's skills directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// A symlink to the skill in the plugin cache; follows updates immediately.
+    #[default]
+    Symlink,
+    /// A copy of the skill directory, refreshed when the plugin is updated.
+    Copy,
+}
+
+impl LinkMode {
+    /// Get the stable identifier for this mode, as used on the command line.
+    pub fn id(&self) -> &'static str {
+        match self {
+            LinkMode::Symlink => "symlink",
+            LinkMode::Copy => "copy",
+        }
+    }
+
+    /// Look up a mode by its identifier.
+    pub fn from_id(id: &str) -> Option<LinkMode> {
+        Self::all().iter().copied().find(|m| m.id() == id)
+    }
+
+    /// Get all link modes.
+    pub fn all() -> &'static [LinkMode] {
+        &[LinkMode::Symlink, LinkMode::Copy]
+    }
+}
+
+/// How links are named inside a target's skills directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Naming {
+    /// `owner:repo:skill`, which never collides between plugins.
+    #[default]
+    Qualified,
+    /// Just the skill name, for tools that show the directory name as-is.
+    Plain,
+}
+
+impl Naming {
+    /// Get the stable identifier for this scheme, as used in the config file.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Naming::Qualified => "qualified",
+            Naming::Plain => "plain",
+        }
+    }
+}
+
+/// A directory skills can be linked into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkTarget {
+    /// Stable identifier, as used on the command line and in manifests.
+    pub name: String,
+    /// Name shown in the TUI.
+    pub display_name: String,
+    /// The skills directory.
+    pub dir: PathBuf,
+    /// How skills are linked unless asked otherwise.
+    pub mode: LinkMode,
+    pub naming: Naming,
+    /// Write symlinks relative to the skills directory instead of absolute ones.
+    pub relative: bool,
+    /// The project this target belongs to; `None` for targets in the home directory.
+    pub project: Option<PathBuf>,
+}
+
+impl LinkTarget {
+    /// The targets skir knows about without any configuration.
+    pub fn builtin() -> Vec<LinkTarget> {
+        let home = dirs::home_dir().unwrap_or_default();
+        vec![
+            LinkTarget {
+                name: "claude".to_string(),
+                display_name: "Claude Code".to_string(),
+                dir: home.join(".claude").join("skills"),
+                mode: LinkMode::Symlink,
+                naming: Naming::Qualified,
+                relative: false,
+                project: None,
+            },
+            LinkTarget {
+                name: "codex".to_string(),
+                display_name: "Codex".to_string(),
+                dir: home.join(".codex").join("skills"),
+                mode: LinkMode::Symlink,
+                naming: Naming::Qualified,
+                relative: false,
+                project: None,
+            },
+        ]
+    }
+
+    /// The targets inside a project: its `.claude/skills` and `.codex/skills`.
+    ///
+    /// Skills are copied, since symlinks would point into the plugin cache
+    /// outside the project and break once it is checked out elsewhere.
+    pub fn project(root: &Path) -> Vec<LinkTarget> {
+        [
+            ("claude", "Claude Code", ".claude"),
+            ("codex", "Codex", ".codex"),
+        ]
+        .into_iter()
+        .map(|(name, display_name, dir)| LinkTarget {
+            name: format!("project-{}", name),
+            display_name: format!("{} (project)", display_name),
+            dir: root.join(dir).join("skills"),
+            mode: LinkMode::Copy,
+            naming: Naming::Qualified,
+            relative: false,
+            project: Some(root.to_path_buf()),
+        })
+        .collect()
+    }
+
+    /// Find the project `dir` is in: the root of its git work tree.
+    ///
+    /// The home directory does not count, since its targets are the global
+    /// ones, and neither do the clones under `cache_dir`.
+    pub fn detect_project(dir: &Path, cache_dir: &Path) -> Option<PathBuf> {
+        let root = git_toplevel(dir)?.canonicalize().ok()?;
+        let is_home = dirs::home_dir()
+            .and_then(|h| h.canonicalize().ok())
+            .is_some_and(|h| root == h);
+        let is_cached = cache_dir.canonicalize().is_ok_and(|c| root.starts_with(c));
+        (!is_home && !is_cached).then_some(root)
+    }
+
+    /// Get all configured link targets.
+    ///
+    /// There are none if the settings cannot be loaded; skir reports that at startup.
+    pub fn all() -> &'static [LinkTarget] {
+        Settings::get().map_or(&[], |settings| &settings.targets)
+    }
+
+    /// Get the stable identifier for this target, as used on the command line.
+    pub fn id(&self) -> &str {
+        &self.name
+    }
+
+    /// Look up a configured target by its identifier.
+    pub fn from_id(id: &str) -> Option<&'static LinkTarget> {
+        Self::all().iter().find(|t| t.id() == id)
+    }
+
+    /// Get the display name for this target.
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// Get the project whose targets are configured, if any.
+    pub fn current_project() -> Option<&'static Path> {
+        Self::all().iter().find_map(|t| t.project.as_deref())
+    }
+}
@@ -0,0 +1,106 @@
+//! Git fixtures shared by tests that need a real repository.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use super::source::{GitRef, GitSource};
+use super::target::{LinkMode, LinkTarget, Naming};
+
+/// Run git in `dir` with a fixed identity.
+pub(crate) fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=skir", "-c", "user.email=skir@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Create a repository with one skill and commit it, returning the commit.
+pub(crate) fn init_repo(dir: &Path) -> String {
+    fs::create_dir_all(dir.join("pdf")).unwrap();
+    fs::write(dir.join("pdf/SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+    git(dir, &["init", "--quiet", "--initial-branch=main"]);
+    commit_all(dir, "initial")
+}
+
+/// Commit every change in `dir`, returning the new commit.
+pub(crate) fn commit_all(dir: &Path, message: &str) -> String {
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "--quiet", "--allow-empty", "-m", message]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+/// A source for a repository on disk, as if it were `me/<name>` on GitHub.
+pub(crate) fn repo_source(repo: &Path, reference: Option<GitRef>) -> GitSource {
+    GitSource {
+        host: "github.com".to_string(),
+        owner: "me".to_string(),
+        repo: repo.file_name().unwrap().to_string_lossy().to_string(),
+        url: format!("file://{}", repo.display()),
+        reference,
+        subdir: None,
+    }
+}
+
+/// A symlink target named `test` whose skills directory is `dir`.
+pub(crate) fn target(dir: &Path) -> LinkTarget {
+    LinkTarget {
+        name: "test".to_string(),
+        display_name: "Test".to_string(),
+        dir: dir.to_path_buf(),
+        mode: LinkMode::Symlink,
+        naming: Naming::Qualified,
+        relative: false,
+        project: None,
+    }
+}
+
+/// Targets named `claude` and `codex`, like the built-in ones, inside `dir`.
+pub(crate) fn targets(dir: &Path) -> Vec<LinkTarget> {
+    ["claude", "codex"]
+        .into_iter()
+        .map(|name| LinkTarget {
+            name: name.to_string(),
+            ..target(&dir.join(name))
+        })
+        .collect()
+}
+
+/// Create a bare repository at `bare` with one skill, published from a
+/// working clone at `work`, returning the commit.
+///
+/// Further commits made in `work` reach `bare` with [`push`].
+pub(crate) fn init_bare(work: &Path, bare: &Path) -> String {
+    let commit = init_repo(work);
+    let parent = bare.parent().unwrap();
+    fs::create_dir_all(parent).unwrap();
+    git(
+        parent,
+        &[
+            "clone",
+            "--quiet",
+            "--bare",
+            &work.display().to_string(),
+            &bare.display().to_string(),
+        ],
+    );
+    git(
+        work,
+        &["remote", "add", "origin", &bare.display().to_string()],
+    );
+    commit
+}
+
+/// Push the working clone's `main` to its bare origin.
+pub(crate) fn push(work: &Path) {
+    git(work, &["push", "--quiet", "origin", "main"]);
+}
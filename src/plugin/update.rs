@@ -0,0 +1,218 @@
+//! Fetched updates that can be reviewed before they are applied.
+
+use std::collections::BTreeMap;
+
+/// A commit an update would bring in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingCommit {
+    /// Full commit SHA.
+    pub sha: String,
+    /// First line of the commit message.
+    pub summary: String,
+}
+
+/// How a file changes in an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl FileStatus {
+    /// Single-letter marker, as in `git status --short`.
+    pub fn marker(&self) -> char {
+        match self {
+            FileStatus::Added => 'A',
+            FileStatus::Modified => 'M',
+            FileStatus::Deleted => 'D',
+        }
+    }
+}
+
+/// The change to a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the repository root.
+    pub path: String,
+    pub status: FileStatus,
+    /// Unified diff of this file.
+    pub diff: String,
+}
+
+/// Changed files belonging to one skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillChange {
+    /// Skill name, or `None` for files outside any skill.
+    pub skill: Option<String>,
+    pub files: Vec<FileChange>,
+}
+
+/// An update that has been fetched but not applied yet.
+#[derive(Debug, Clone)]
+pub struct PendingUpdate {
+    /// Commit currently checked out.
+    pub from: String,
+    /// Commit the update fast-forwards to.
+    pub to: String,
+    /// Incoming commits, newest first.
+    pub commits: Vec<IncomingCommit>,
+    /// Changed files, grouped by skill.
+    pub changes: Vec<SkillChange>,
+}
+
+impl PendingUpdate {
+    /// Check if there is nothing to apply.
+    pub fn is_up_to_date(&self) -> bool {
+        self.commits.is_empty()
+    }
+}
+
+/// Parse `git log --format=%H%x09%s` output.
+pub(crate) fn parse_log(output: &str) -> Vec<IncomingCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (sha, summary) = line.split_once('\t')?;
+            Some(IncomingCommit {
+                sha: sha.to_string(),
+                summary: summary.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Split `git diff` output into per-file changes.
+pub(crate) fn parse_diff(output: &str) -> Vec<FileChange> {
+    let mut files: Vec<FileChange> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            // Header is `a/<path> b/<path>`; the new path follows the last " b/"
+            let path = header
+                .rsplit_once(" b/")
+                .map(|(_, path)| path)
+                .unwrap_or(header)
+                .to_string();
+            files.push(FileChange {
+                path,
+                status: FileStatus::Modified,
+                diff: String::new(),
+            });
+        }
+
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("new file mode") {
+            file.status = FileStatus::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.status = FileStatus::Deleted;
+        }
+        file.diff.push_str(line);
+        file.diff.push('\n');
+    }
+
+    files
+}
+
+/// Group changed files by the skill directory containing them.
+///
+/// `skill_dirs` maps skill names to their directory relative to the repository
+/// root (`""` for a skill at the root). Files are assigned to the innermost
+/// matching directory; files outside every skill come last.
+pub(crate) fn group_by_skill(
+    files: Vec<FileChange>,
+    skill_dirs: &[(String, String)],
+) -> Vec<SkillChange> {
+    let mut by_skill: BTreeMap<String, Vec<FileChange>> = BTreeMap::new();
+    let mut other = Vec::new();
+
+    for file in files {
+        let owner = skill_dirs
+            .iter()
+            .filter(|(_, dir)| dir.is_empty() || file.path.starts_with(&format!("{}/", dir)))
+            .max_by_key(|(_, dir)| dir.len());
+        match owner {
+            Some((name, _)) => by_skill.entry(name.clone()).or_default().push(file),
+            None => other.push(file),
+        }
+    }
+
+    let mut changes: Vec<SkillChange> = by_skill
+        .into_iter()
+        .map(|(skill, files)| SkillChange {
+            skill: Some(skill),
+            files,
+        })
+        .collect();
+    if !other.is_empty() {
+        changes.push(SkillChange {
+            skill: None,
+            files: other,
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/skills/foo/SKILL.md b/skills/foo/SKILL.md
+index 1111111..2222222 100644
+--- a/skills/foo/SKILL.md
++++ b/skills/foo/SKILL.md
+@@ -1 +1 @@
+-old
++new
+diff --git a/skills/bar/script.py b/skills/bar/script.py
+new file mode 100644
+--- /dev/null
++++ b/skills/bar/script.py
+@@ -0,0 +1 @@
++print()
+diff --git a/README.md b/README.md
+deleted file mode 100644
+--- a/README.md
++++ /dev/null
+@@ -1 +0,0 @@
+-readme
+";
+
+    #[test]
+    fn test_parse_log() {
+        let commits = parse_log("abc\tFix typo\ndef\tAdd: tabs\tinside\n");
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "abc");
+        assert_eq!(commits[1].summary, "Add: tabs\tinside");
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let files = parse_diff(DIFF);
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].path, "skills/foo/SKILL.md");
+        assert_eq!(files[0].status, FileStatus::Modified);
+        assert!(files[0].diff.contains("+new"));
+        assert_eq!(files[1].status, FileStatus::Added);
+        assert_eq!(files[2].path, "README.md");
+        assert_eq!(files[2].status, FileStatus::Deleted);
+    }
+
+    #[test]
+    fn test_group_by_skill() {
+        let skill_dirs = vec![
+            ("foo".to_string(), "skills/foo".to_string()),
+            ("bar".to_string(), "skills/bar".to_string()),
+        ];
+        let changes = group_by_skill(parse_diff(DIFF), &skill_dirs);
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].skill.as_deref(), Some("bar"));
+        assert_eq!(changes[1].skill.as_deref(), Some("foo"));
+        assert_eq!(changes[2].skill, None);
+        assert_eq!(changes[2].files[0].path, "README.md");
+    }
+}
@@ -0,0 +1,174 @@
+//! All-skills view rendering: every skill of every plugin, with its link state per target.
+
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use super::theme;
+use crate::app::App;
+use crate::plugin::{LinkStatus, LinkTarget, Skill};
+
+/// Widest the name and plugin columns get, in characters.
+const MAX_NAME_WIDTH: usize = 40;
+const MAX_PLUGIN_WIDTH: usize = 24;
+
+/// Cell text and color for a skill's link in one target.
+fn link_cell(skill: &Skill, target: &LinkTarget) -> (&'static str, Color) {
+    if skill.is_link_broken(target) {
+        return ("broken", theme::ERROR);
+    }
+    match skill.link_status(target) {
+        LinkStatus::NotLinked => ("-", theme::TEXT_DIM),
+        LinkStatus::Symlinked => ("linked", theme::SUCCESS),
+        LinkStatus::Copied => ("copied", theme::SUCCESS),
+        LinkStatus::Outdated => ("outdated", theme::WARNING),
+        LinkStatus::Modified => ("edited", theme::WARNING),
+    }
+}
+
+/// Pad or cut `text` to exactly `width` characters.
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}~", cut)
+    } else {
+        format!("{:<width$}", text)
+    }
+}
+
+/// Draw every skill of every plugin as a table.
+pub fn draw_all_skills(frame: &mut Frame, area: Rect, app: &App) {
+    let skills = app.all_skills();
+    let filtered = app.filtered_all_skills_indices();
+    let targets = LinkTarget::all();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(area);
+
+    let target_label = match app.all_skills_target.and_then(|i| targets.get(i)) {
+        Some(target) => target.display_name().to_string(),
+        None => "all".to_string(),
+    };
+    let header = Paragraph::new(format!(
+        "All skills ({} of {})  filter: {}  targets: {}",
+        filtered.len(),
+        skills.len(),
+        app.all_skills_filter.label(),
+        target_label
+    ))
+    .style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    if filtered.is_empty() {
+        let text = if skills.is_empty() {
+            "No skills installed."
+        } else {
+            "No skills match the filter."
+        };
+        let message = Paragraph::new(text).style(Style::default().fg(theme::TEXT_DIM));
+        frame.render_widget(message, chunks[2]);
+        return;
+    }
+
+    let plugin_label = |i: usize| {
+        let (plugin, _) = skills[i];
+        format!("{}/{}", plugin.owner, plugin.name())
+    };
+    let name_width = filtered
+        .iter()
+        .map(|&i| skills[i].1.qualified_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_WIDTH);
+    let plugin_width = filtered
+        .iter()
+        .map(|&i| plugin_label(i).chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_PLUGIN_WIDTH);
+    let target_widths: Vec<usize> = targets
+        .iter()
+        .map(|t| t.name.chars().count().max("outdated".len()))
+        .collect();
+
+    // Column headings, with the target in view highlighted
+    let mut columns = vec![
+        Span::raw("  "),
+        Span::raw(fit("Skill", name_width)),
+        Span::raw("  "),
+        Span::raw(fit("Plugin", plugin_width)),
+    ];
+    for (i, (target, width)) in targets.iter().zip(&target_widths).enumerate() {
+        let color = if app.all_skills_target == Some(i) {
+            theme::ACCENT
+        } else {
+            theme::TEXT_DIM
+        };
+        columns.push(Span::styled(
+            format!("  {}", fit(&target.name, *width)),
+            Style::default().fg(color),
+        ));
+    }
+    columns.push(Span::raw("  Description"));
+    frame.render_widget(
+        Paragraph::new(Line::from(columns)).style(Style::default().fg(theme::TEXT_DIM).bold()),
+        chunks[1],
+    );
+
+    let items: Vec<ListItem> = filtered
+        .iter()
+        .map(|&i| {
+            let (_, skill) = skills[i];
+            let is_selected = i == app.all_skills_selection;
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "> " } else { "  " },
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT_DIM
+                    }),
+                ),
+                Span::styled(
+                    fit(&skill.qualified_name(), name_width),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+                Span::styled(
+                    format!("  {}", fit(&plugin_label(i), plugin_width)),
+                    Style::default().fg(theme::TEXT_DIM),
+                ),
+            ];
+            for (target, width) in targets.iter().zip(&target_widths) {
+                let (label, color) = link_cell(skill, target);
+                spans.push(Span::styled(
+                    format!("  {}", fit(label, *width)),
+                    Style::default().fg(color),
+                ));
+            }
+            if let Some(description) = skill.description().and_then(|d| d.lines().next()) {
+                spans.push(Span::styled(
+                    format!("  {}", description),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let selected = filtered.iter().position(|&i| i == app.all_skills_selection);
+    let mut list_state = ListState::default().with_selected(selected);
+    frame.render_stateful_widget(List::new(items), chunks[2], &mut list_state);
+}
@@ -0,0 +1,107 @@
+//! Available view rendering: the plugins offered by added marketplaces.
+
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use super::theme;
+use crate::app::App;
+
+/// Draw the entries of every added marketplace.
+pub fn draw_available(frame: &mut Frame, area: Rect, app: &App) {
+    let entries = app.available_entries();
+    let filtered = app.filtered_available_indices();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header_text = if app.search_active && !app.search_query.is_empty() {
+        format!("Available ({} of {})", filtered.len(), entries.len())
+    } else {
+        format!(
+            "Available ({} from {} marketplaces)",
+            entries.len(),
+            app.marketplaces.len()
+        )
+    };
+    let header = Paragraph::new(header_text).style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    if entries.is_empty() {
+        let text = if app.marketplaces.is_empty() {
+            "No marketplaces added. Press 'a' to add one."
+        } else {
+            "The added marketplaces list no plugins."
+        };
+        let message = Paragraph::new(text).style(Style::default().fg(theme::TEXT_DIM));
+        frame.render_widget(message, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = filtered
+        .iter()
+        .map(|&i| {
+            let (marketplace, entry) = entries[i];
+            let is_selected = i == app.available_selection;
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "> " } else { "  " },
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT_DIM
+                    }),
+                ),
+                Span::styled(
+                    entry.name.clone(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+            ];
+
+            if let Some(version) = &entry.version {
+                spans.push(Span::styled(
+                    format!(" v{}", version),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            if app.is_entry_installing(marketplace, entry) {
+                spans.push(Span::styled(
+                    " [installing]",
+                    Style::default().fg(theme::ACCENT),
+                ));
+            } else if app.is_entry_installed(marketplace, entry) {
+                spans.push(Span::styled(
+                    " [installed]",
+                    Style::default().fg(theme::SUCCESS),
+                ));
+            }
+
+            spans.push(Span::styled(
+                format!("  @{}", marketplace.name),
+                Style::default().fg(theme::TEXT_DIM),
+            ));
+
+            if let Some(description) = entry.description.as_deref().and_then(|d| d.lines().next()) {
+                spans.push(Span::styled(
+                    format!("  {}", description),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let selected = filtered.iter().position(|&i| i == app.available_selection);
+    let mut list_state = ListState::default().with_selected(selected);
+    frame.render_stateful_widget(List::new(items), chunks[1], &mut list_state);
+}
@@ -0,0 +1,97 @@
+//! Skill detail rendering.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+
+use super::theme;
+use crate::app::App;
+use crate::plugin::Severity;
+
+/// Build a `label: value` line.
+fn field(label: &str, value: impl Into<String>) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{:<14}", label),
+            Style::default().fg(theme::TEXT_DIM),
+        ),
+        Span::styled(value.into(), Style::default().fg(theme::TEXT)),
+    ])
+}
+
+/// Draw the metadata of the selected skill.
+pub fn draw_skill_detail(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(skill) = app.selected_skill() else {
+        return;
+    };
+    let metadata = &skill.metadata;
+    let unset = || "-".to_string();
+
+    let mut lines = vec![
+        field("Skill", skill.qualified_name()),
+        field("Path", skill.path.display().to_string()),
+        field("Name", metadata.name.clone().unwrap_or_else(unset)),
+        field("Version", metadata.version.clone().unwrap_or_else(unset)),
+        field("License", metadata.license.clone().unwrap_or_else(unset)),
+    ];
+
+    let tools = if metadata.allowed_tools.is_empty() {
+        unset()
+    } else {
+        metadata.allowed_tools.join(", ")
+    };
+    lines.push(field("Allowed tools", tools));
+
+    for (key, value) in &metadata.extra {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        lines.push(field(key, value));
+    }
+
+    if !skill.diagnostics().is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            "Problems",
+            Style::default().fg(theme::TEXT_DIM),
+        ));
+    }
+    for diagnostic in skill.diagnostics() {
+        let color = match diagnostic.severity {
+            Severity::Error => theme::ERROR,
+            Severity::Warning => theme::WARNING,
+        };
+        let location = diagnostic
+            .line
+            .map(|l| format!("line {}: ", l))
+            .unwrap_or_default();
+        lines.push(Line::styled(
+            format!(
+                "{}{}: {}",
+                location, diagnostic.severity, diagnostic.message
+            ),
+            Style::default().fg(color),
+        ));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        "Description",
+        Style::default().fg(theme::TEXT_DIM),
+    ));
+    match skill.description() {
+        Some(description) => {
+            lines.extend(
+                description
+                    .lines()
+                    .map(|l| Line::styled(l.to_string(), Style::default().fg(theme::TEXT))),
+            );
+        }
+        None => lines.push(Line::styled(unset(), Style::default().fg(theme::TEXT))),
+    }
+
+    let detail = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(detail, area);
+}
@@ -0,0 +1,111 @@
+//! Link health view rendering.
+
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use super::theme;
+use crate::app::App;
+use crate::plugin::Health;
+
+/// Badge text and color for a health state.
+fn badge(health: Health) -> (&'static str, Color) {
+    match health {
+        Health::Healthy => ("[ok]", theme::SUCCESS),
+        Health::Broken => ("[broken]", theme::ERROR),
+        Health::OutsideCache => ("[outside cache]", theme::WARNING),
+        Health::NotInstalled => ("[not installed]", theme::TEXT_DIM),
+        Health::Unmanaged => ("[unmanaged]", theme::WARNING),
+        Health::Duplicate => ("[duplicate]", theme::WARNING),
+    }
+}
+
+/// Draw the entries of every link target with their health.
+pub fn draw_health(frame: &mut Frame, area: Rect, app: &App) {
+    let problems = app.health.iter().filter(|e| e.is_problem()).count();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(format!(
+        "Link health ({} entries, {} problems)",
+        app.health.len(),
+        problems
+    ))
+    .style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    if app.health.is_empty() {
+        let message = Paragraph::new("No links in any target directory.")
+            .style(Style::default().fg(theme::TEXT_DIM));
+        frame.render_widget(message, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .health
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let is_selected = i == app.health_selection;
+            let (label, color) = badge(entry.health);
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "> " } else { "  " },
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT_DIM
+                    }),
+                ),
+                Span::styled(format!("{:<16}", label), Style::default().fg(color)),
+                Span::styled(
+                    entry.name(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+                Span::styled(
+                    format!("  {}", entry.target.display_name()),
+                    Style::default().fg(theme::TEXT_DIM),
+                ),
+            ];
+
+            if let Some(points_to) = &entry.points_to {
+                spans.push(Span::styled(
+                    format!("  -> {}", points_to.display()),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+            if let Some(origin) = &entry.origin {
+                spans.push(Span::styled(
+                    format!("  {}", origin),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            // Show what can be done about the selected entry
+            if is_selected && entry.health != Health::Healthy {
+                let repairs: Vec<&str> = entry.repairs().iter().map(|r| r.id()).collect();
+                let text = if repairs.is_empty() {
+                    "  (fix by hand)".to_string()
+                } else {
+                    format!("  ({})", repairs.join(", "))
+                };
+                spans.push(Span::styled(text, Style::default().fg(theme::ACCENT)));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(app.health_selection));
+    let list = List::new(items);
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+}
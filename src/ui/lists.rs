@@ -5,9 +5,9 @@ use ratatui::{
     widgets::{List, ListItem, ListState, Paragraph},
 };
 
-use crate::app::App;
-use crate::plugin::LinkTarget;
 use super::theme;
+use crate::app::{App, Tab};
+use crate::plugin::{ComponentKind, LinkStatus, LinkTarget, Plugin, Severity, Skill};
 
 /// Create a selection indicator span.
 fn selection_indicator(is_selected: bool) -> Span<'static> {
@@ -23,7 +23,12 @@ fn selection_indicator(is_selected: bool) -> Span<'static> {
 pub fn draw_plugin_list(frame: &mut Frame, area: Rect, app: &mut App) {
     let total_count = app.plugins.len() + app.installing.len();
     let filtered_indices = app.filtered_plugin_indices();
-    let filtered_count = filtered_indices.len() + if app.search_query.is_empty() { app.installing.len() } else { 0 };
+    let filtered_count = filtered_indices.len()
+        + if app.search_query.is_empty() {
+            app.installing.len()
+        } else {
+            0
+        };
 
     let header_text = if app.search_active && !app.search_query.is_empty() {
         format!("Plugins ({} of {})", filtered_count, total_count)
@@ -38,8 +43,7 @@ pub fn draw_plugin_list(frame: &mut Frame, area: Rect, app: &mut App) {
         .split(area);
 
     // Draw header
-    let header = Paragraph::new(header_text)
-        .style(Style::default().fg(theme::TEXT_DIM));
+    let header = Paragraph::new(header_text).style(Style::default().fg(theme::TEXT_DIM));
     frame.render_widget(header, chunks[0]);
 
     if app.plugins.is_empty() && app.installing.is_empty() {
@@ -59,19 +63,54 @@ pub fn draw_plugin_list(frame: &mut Frame, area: Rect, app: &mut App) {
             let total = skills.len();
             let linked = skills.iter().filter(|s| s.is_linked()).count();
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 selection_indicator(is_selected),
                 Span::styled(
                     format!("{}/{}", plugin.owner, plugin.name()),
-                    Style::default().fg(if is_selected { theme::ACCENT } else { theme::TEXT }),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
                 ),
-                Span::styled(
-                    format!("  [{}/{} linked]", linked, total),
+            ];
+
+            if let Some(version) = plugin.manifest.as_ref().and_then(|m| m.version.as_ref()) {
+                spans.push(Span::styled(
+                    format!(" v{}", version),
                     Style::default().fg(theme::TEXT_DIM),
-                ),
-            ]);
+                ));
+            }
 
-            ListItem::new(line)
+            if plugin.is_local() {
+                spans.push(Span::styled(
+                    " [local]",
+                    Style::default().fg(theme::SUCCESS),
+                ));
+            }
+
+            // Show the pinned ref, if any
+            if let Some(reference) = &plugin.reference {
+                spans.push(Span::styled(
+                    format!(" {}", reference),
+                    Style::default().fg(theme::ACCENT),
+                ));
+            }
+
+            // Show how far behind upstream the plugin is
+            if let Some(count) = app.behind.get(&plugin.path) {
+                spans.push(Span::styled(
+                    format!("  [{} new]", count),
+                    Style::default().fg(theme::ACCENT),
+                ));
+            }
+
+            spans.push(Span::styled(
+                format!("  [{}/{} linked]", linked, total),
+                Style::default().fg(theme::TEXT_DIM),
+            ));
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -85,7 +124,11 @@ pub fn draw_plugin_list(frame: &mut Frame, area: Rect, app: &mut App) {
                 selection_indicator(is_selected),
                 Span::styled(
                     url.clone(),
-                    Style::default().fg(if is_selected { theme::ACCENT } else { theme::TEXT }),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
                 ),
                 Span::styled("  [installing]", Style::default().fg(theme::ACCENT)),
             ]);
@@ -104,20 +147,52 @@ pub fn draw_plugin_list(frame: &mut Frame, area: Rect, app: &mut App) {
     frame.render_stateful_widget(list, chunks[1], &mut list_state);
 }
 
-/// Draw the skill list for the selected plugin.
+/// Build the skill list header: the plugin name, then one tab per item kind
+/// with its count.
+fn tab_header(plugin: &Plugin, current: Tab, filtered: Option<usize>) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        format!("{}/{}", plugin.owner, plugin.name()),
+        Style::default().fg(theme::TEXT_DIM),
+    )];
+    for tab in Tab::all() {
+        let total = match tab {
+            Tab::Skills => plugin.skills().len(),
+            Tab::Components(kind) => plugin.components_of(kind).count(),
+        };
+        let (text, style) = match filtered {
+            Some(count) if tab == current => (
+                format!("  {} ({} of {})", tab.label(), count, total),
+                Style::default().fg(theme::ACCENT).bold(),
+            ),
+            _ if tab == current => (
+                format!("  {} ({})", tab.label(), total),
+                Style::default().fg(theme::ACCENT).bold(),
+            ),
+            _ => (
+                format!("  {} ({})", tab.label(), total),
+                Style::default().fg(theme::TEXT_DIM),
+            ),
+        };
+        spans.push(Span::styled(text, style));
+    }
+    Line::from(spans)
+}
+
+/// Draw the skill list for the selected plugin, or the components of the current tab.
 pub fn draw_skill_list(frame: &mut Frame, area: Rect, app: &mut App) {
     let Some(plugin) = app.selected_plugin() else {
         return;
     };
 
+    if let Tab::Components(kind) = app.tab {
+        draw_component_list(frame, area, app, kind);
+        return;
+    }
+
     let skills = plugin.skills();
     let filtered_indices = app.filtered_skill_indices();
-
-    let header_text = if app.search_active && !app.search_query.is_empty() {
-        format!("{}/{} ({} of {} skills)", plugin.owner, plugin.name(), filtered_indices.len(), skills.len())
-    } else {
-        format!("{}/{}", plugin.owner, plugin.name())
-    };
+    let filtered =
+        (app.search_active && !app.search_query.is_empty()).then_some(filtered_indices.len());
 
     // Split area for header and list
     let chunks = Layout::default()
@@ -125,14 +200,20 @@ pub fn draw_skill_list(frame: &mut Frame, area: Rect, app: &mut App) {
         .constraints([Constraint::Length(1), Constraint::Min(1)])
         .split(area);
 
-    // Draw header
-    let header = Paragraph::new(header_text)
-        .style(Style::default().fg(theme::TEXT_DIM));
-    frame.render_widget(header, chunks[0]);
+    // Draw header, with the number of marked skills if any
+    let marked = app.marked_skill_indices();
+    let mut header = tab_header(plugin, app.tab, filtered);
+    if app.has_marks() {
+        header.push_span(Span::styled(
+            format!("  [{} marked]", marked.len()),
+            Style::default().fg(theme::ACCENT),
+        ));
+    }
+    frame.render_widget(Paragraph::new(header), chunks[0]);
 
     if skills.is_empty() {
-        let message = Paragraph::new("No skills in this plugin.")
-            .style(Style::default().fg(theme::TEXT_DIM));
+        let message =
+            Paragraph::new("No skills in this plugin.").style(Style::default().fg(theme::TEXT_DIM));
         frame.render_widget(message, chunks[1]);
         return;
     }
@@ -148,15 +229,47 @@ pub fn draw_skill_list(frame: &mut Frame, area: Rect, app: &mut App) {
             let is_selected = i == app.selected_skill;
 
             // Count how many targets this skill is linked to
-            let linked_count = targets.iter().filter(|t| skill.is_linked_to(**t)).count();
+            let linked_count = targets.iter().filter(|t| skill.is_linked_to(t)).count();
 
-            let mut spans = vec![
-                selection_indicator(is_selected),
-                Span::styled(
-                    skill.name.clone(),
-                    Style::default().fg(if is_selected { theme::ACCENT } else { theme::TEXT }),
-                ),
-            ];
+            let mut spans = vec![selection_indicator(is_selected)];
+            if app.has_marks() {
+                let (mark, color) = if marked.contains(&i) {
+                    ("[x] ", theme::ACCENT)
+                } else {
+                    ("[ ] ", theme::TEXT_DIM)
+                };
+                spans.push(Span::styled(mark, Style::default().fg(color)));
+            }
+            spans.push(Span::styled(
+                skill.name.clone(),
+                Style::default().fg(if is_selected {
+                    theme::ACCENT
+                } else {
+                    theme::TEXT
+                }),
+            ));
+
+            if let Some(version) = &skill.metadata.version {
+                spans.push(Span::styled(
+                    format!(" v{}", version),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            // Flag skills that fail validation
+            let errors = skill.diagnostic_count(Severity::Error);
+            let warnings = skill.diagnostic_count(Severity::Warning);
+            if errors > 0 {
+                spans.push(Span::styled(
+                    format!("  [! {} errors]", errors),
+                    Style::default().fg(theme::ERROR),
+                ));
+            } else if warnings > 0 {
+                spans.push(Span::styled(
+                    format!("  [! {} warnings]", warnings),
+                    Style::default().fg(theme::WARNING),
+                ));
+            }
 
             // Show link status with count
             if linked_count > 0 {
@@ -169,9 +282,20 @@ pub fn draw_skill_list(frame: &mut Frame, area: Rect, app: &mut App) {
                 spans.push(Span::styled(status_text, Style::default().fg(color)));
             }
 
-            // Show description for selected skill
+            // Show links into the current project separately
+            if targets
+                .iter()
+                .any(|t| t.project.is_some() && skill.is_linked_to(t))
+            {
+                spans.push(Span::styled(
+                    "  [project]",
+                    Style::default().fg(theme::SUCCESS),
+                ));
+            }
+
+            // Show the first line of the description for selected skill
             if is_selected {
-                if let Some(desc) = &skill.description {
+                if let Some(desc) = skill.description().and_then(|d| d.lines().next()) {
                     spans.push(Span::styled(
                         format!("  {}", desc),
                         Style::default().fg(theme::TEXT_DIM),
@@ -193,18 +317,107 @@ pub fn draw_skill_list(frame: &mut Frame, area: Rect, app: &mut App) {
     frame.render_stateful_widget(list, chunks[1], &mut list_state);
 }
 
-/// Draw the link target selection view.
-pub fn draw_link_target_select(frame: &mut Frame, area: Rect, app: &App) {
+/// Draw the commands, agents, hooks or MCP servers of the selected plugin.
+fn draw_component_list(frame: &mut Frame, area: Rect, app: &App, kind: ComponentKind) {
     let Some(plugin) = app.selected_plugin() else {
         return;
     };
 
-    let skills = plugin.skills();
-    if skills.is_empty() || app.selected_skill >= skills.len() {
+    let components = app.tab_components();
+    let filtered_indices = app.filtered_component_indices();
+    let filtered =
+        (app.search_active && !app.search_query.is_empty()).then_some(filtered_indices.len());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+    frame.render_widget(
+        Paragraph::new(tab_header(plugin, app.tab, filtered)),
+        chunks[0],
+    );
+
+    if components.is_empty() {
+        let message = Paragraph::new(format!(
+            "No {} in this plugin.",
+            kind.label().to_lowercase()
+        ))
+        .style(Style::default().fg(theme::TEXT_DIM));
+        frame.render_widget(message, chunks[1]);
         return;
     }
 
-    let skill = &skills[app.selected_skill];
+    let targets: Vec<&LinkTarget> = LinkTarget::all()
+        .iter()
+        .filter(|t| kind.dir_for(t).is_some())
+        .collect();
+    let items: Vec<ListItem> = filtered_indices
+        .iter()
+        .map(|&i| {
+            let component = components[i];
+            let is_selected = i == app.selected_component;
+
+            let mut spans = vec![
+                selection_indicator(is_selected),
+                Span::styled(
+                    component.name.clone(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+            ];
+
+            let linked_count = targets.iter().filter(|t| component.is_linked_to(t)).count();
+            if linked_count > 0 {
+                let color = if linked_count == targets.len() {
+                    theme::SUCCESS
+                } else {
+                    theme::ACCENT
+                };
+                spans.push(Span::styled(
+                    format!("  [{}/{} linked]", linked_count, targets.len()),
+                    Style::default().fg(color),
+                ));
+            }
+
+            // Hooks and MCP servers are summarized by their configuration, so always show it
+            if (is_selected || !kind.is_linkable())
+                && let Some(desc) = component
+                    .description
+                    .as_deref()
+                    .and_then(|d| d.lines().next())
+            {
+                spans.push(Span::styled(
+                    format!("  {}", desc),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let selected_position = filtered_indices
+        .iter()
+        .position(|&i| i == app.selected_component);
+    let mut list_state = ListState::default().with_selected(selected_position);
+    frame.render_stateful_widget(List::new(items), chunks[1], &mut list_state);
+}
+
+/// Draw the link target selection view.
+pub fn draw_link_target_select(frame: &mut Frame, area: Rect, app: &App) {
+    if app.tab != Tab::Skills {
+        draw_component_target_select(frame, area, app);
+        return;
+    }
+
+    let skills = app.selected_skills();
+    let [skill] = skills.as_slice() else {
+        draw_bulk_target_select(frame, area, app, &skills);
+        return;
+    };
 
     // Split area for header and list
     let chunks = Layout::default()
@@ -224,21 +437,28 @@ pub fn draw_link_target_select(frame: &mut Frame, area: Rect, app: &App) {
         .enumerate()
         .map(|(i, target)| {
             let is_selected = i == app.link_target_selection;
-            let is_linked = skill.is_linked_to(*target);
+            let status = skill.link_status(target);
 
             let mut spans = vec![
                 selection_indicator(is_selected),
                 Span::styled(
                     target.display_name(),
-                    Style::default().fg(if is_selected { theme::ACCENT } else { theme::TEXT }),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
                 ),
             ];
 
-            if is_linked {
-                spans.push(Span::styled("  [linked]", Style::default().fg(theme::SUCCESS)));
-            } else {
-                spans.push(Span::styled("  [not linked]", Style::default().fg(theme::TEXT_DIM)));
-            }
+            let (label, color) = match status {
+                LinkStatus::NotLinked => ("  [not linked]", theme::TEXT_DIM),
+                LinkStatus::Symlinked => ("  [linked]", theme::SUCCESS),
+                LinkStatus::Copied => ("  [copied]", theme::SUCCESS),
+                LinkStatus::Outdated => ("  [copy outdated]", theme::WARNING),
+                LinkStatus::Modified => ("  [copy edited locally]", theme::WARNING),
+            };
+            spans.push(Span::styled(label, Style::default().fg(color)));
 
             ListItem::new(Line::from(spans))
         })
@@ -248,3 +468,97 @@ pub fn draw_link_target_select(frame: &mut Frame, area: Rect, app: &App) {
     let list = List::new(items);
     frame.render_stateful_widget(list, chunks[1], &mut list_state);
 }
+
+/// Draw the link target selection view for several marked skills, with how
+/// many of them are linked to each target.
+fn draw_bulk_target_select(frame: &mut Frame, area: Rect, app: &App, skills: &[&Skill]) {
+    if skills.is_empty() {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(format!("Link {} skills to:", skills.len()))
+        .style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = LinkTarget::all()
+        .iter()
+        .enumerate()
+        .map(|(i, target)| {
+            let is_selected = i == app.link_target_selection;
+            let linked = skills.iter().filter(|s| s.is_linked_to(target)).count();
+            let (label, color) = match linked {
+                0 => ("  [not linked]".to_string(), theme::TEXT_DIM),
+                n if n == skills.len() => ("  [all linked]".to_string(), theme::SUCCESS),
+                n => (format!("  [{}/{} linked]", n, skills.len()), theme::ACCENT),
+            };
+
+            ListItem::new(Line::from(vec![
+                selection_indicator(is_selected),
+                Span::styled(
+                    target.display_name(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+                Span::styled(label, Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(app.link_target_selection));
+    frame.render_stateful_widget(List::new(items), chunks[1], &mut list_state);
+}
+
+/// Draw the link target selection view for a command or agent.
+fn draw_component_target_select(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(component) = app.selected_component() else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(format!("Link {} to:", component.name))
+        .style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = LinkTarget::all()
+        .iter()
+        .enumerate()
+        .map(|(i, target)| {
+            let is_selected = i == app.link_target_selection;
+            let (label, color) = if component.link_path_for(target).is_none() {
+                ("  [not supported]", theme::TEXT_DIM)
+            } else if component.is_linked_to(target) {
+                ("  [linked]", theme::SUCCESS)
+            } else {
+                ("  [not linked]", theme::TEXT_DIM)
+            };
+
+            ListItem::new(Line::from(vec![
+                selection_indicator(is_selected),
+                Span::styled(
+                    target.display_name(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+                Span::styled(label, Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(app.link_target_selection));
+    frame.render_stateful_widget(List::new(items), chunks[1], &mut list_state);
+}
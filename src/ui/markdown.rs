@@ -0,0 +1,274 @@
+//! Markdown rendering for the skill preview.
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::prelude::*;
+
+use super::theme;
+
+/// Turns markdown events into styled lines.
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Line<'static>>,
+    /// Spans of the line being built.
+    spans: Vec<Span<'static>>,
+    /// Inline styles in effect, innermost last.
+    styles: Vec<Style>,
+    /// Open lists, with the next number for ordered ones.
+    lists: Vec<Option<u64>>,
+    quote_depth: usize,
+    in_code_block: bool,
+    in_metadata: bool,
+}
+
+impl Renderer {
+    fn style(&self) -> Style {
+        self.styles
+            .last()
+            .copied()
+            .unwrap_or_else(|| Style::default().fg(theme::TEXT))
+    }
+
+    fn push_style(&mut self, f: impl FnOnce(Style) -> Style) {
+        let style = f(self.style());
+        self.styles.push(style);
+    }
+
+    fn push_text(&mut self, text: impl Into<String>, style: Style) {
+        self.spans.push(Span::styled(text.into(), style));
+    }
+
+    /// Finish the current line, if it has any content.
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let mut spans = Vec::new();
+        if self.quote_depth > 0 {
+            spans.push(Span::styled(
+                "│ ".repeat(self.quote_depth),
+                Style::default().fg(theme::TEXT_DIM),
+            ));
+        }
+        spans.append(&mut self.spans);
+        self.lines.push(Line::from(spans));
+    }
+
+    /// Separate blocks with a single empty line.
+    fn blank(&mut self) {
+        self.flush();
+        if self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let color = if level == HeadingLevel::H1 {
+                    theme::ACCENT
+                } else {
+                    theme::TEXT
+                };
+                let marker = "#".repeat(level as usize);
+                self.push_text(format!("{} ", marker), Style::default().fg(theme::TEXT_DIM));
+                self.styles.push(Style::default().fg(color).bold());
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}{}. ", indent, *n - 1)
+                    }
+                    _ => format!("{}• ", indent),
+                };
+                self.push_text(marker, Style::default().fg(theme::ACCENT));
+            }
+            Tag::CodeBlock(kind) => {
+                self.blank();
+                if let CodeBlockKind::Fenced(lang) = kind
+                    && !lang.is_empty()
+                {
+                    self.push_text(format!("  {}", lang), Style::default().fg(theme::TEXT_DIM));
+                    self.flush();
+                }
+                self.in_code_block = true;
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            Tag::TableCell if !self.spans.is_empty() => {
+                self.push_text(" │ ", Style::default().fg(theme::TEXT_DIM));
+            }
+            Tag::Emphasis => self.push_style(|s| s.italic()),
+            Tag::Strong | Tag::TableHead => self.push_style(|s| s.bold()),
+            Tag::Strikethrough => self.push_style(|s| s.crossed_out()),
+            Tag::Link { .. } | Tag::Image { .. } => {
+                self.push_style(|s| s.fg(theme::ACCENT).underlined())
+            }
+            Tag::MetadataBlock(_) => self.in_metadata = true,
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.blank();
+            }
+            TagEnd::Paragraph => {
+                if self.lists.is_empty() {
+                    self.blank();
+                } else {
+                    self.flush();
+                }
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+            }
+            TagEnd::Item | TagEnd::TableHead | TagEnd::TableRow => {
+                if matches!(tag, TagEnd::TableHead) {
+                    self.styles.pop();
+                }
+                self.flush();
+            }
+            TagEnd::Table => self.blank(),
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.blank();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth -= 1;
+                if self.quote_depth == 0 {
+                    self.blank();
+                }
+            }
+            TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Link
+            | TagEnd::Image => {
+                self.styles.pop();
+            }
+            TagEnd::MetadataBlock(_) => self.in_metadata = false,
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            // The frontmatter is shown separately
+            Event::Text(_) if self.in_metadata => {}
+            Event::Text(text) if self.in_code_block => {
+                for line in text.lines() {
+                    self.push_text(format!("    {}", line), Style::default().fg(theme::SUCCESS));
+                    self.flush();
+                }
+            }
+            Event::Text(text) => {
+                let style = self.style();
+                self.push_text(text.into_string(), style);
+            }
+            Event::Code(code) => {
+                self.push_text(code.into_string(), Style::default().fg(theme::SUCCESS))
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.push_text(
+                    html.trim_end().to_string(),
+                    Style::default().fg(theme::TEXT_DIM),
+                );
+            }
+            Event::SoftBreak => self.push_text(" ", self.style()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.push_text("─".repeat(40), Style::default().fg(theme::BORDER));
+                self.blank();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render markdown as styled lines, leaving out YAML frontmatter.
+pub fn render(content: &str) -> Vec<Line<'static>> {
+    let options = Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH;
+
+    let mut renderer = Renderer::default();
+    for event in Parser::new_ext(content, options) {
+        renderer.event(event);
+    }
+    renderer.flush();
+
+    let mut lines = renderer.lines;
+    while lines.last().is_some_and(|l| l.width() == 0) {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(content: &str) -> Vec<String> {
+        render(content).iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_blocks() {
+        let content = "\
+---
+name: pdf
+---
+# PDF
+
+Read **PDFs**.
+
+- one
+- two
+  1. nested
+
+```sh
+pdftotext a.pdf
+```
+";
+        assert_eq!(
+            text(content),
+            vec![
+                "# PDF",
+                "",
+                "Read PDFs.",
+                "",
+                "• one",
+                "• two",
+                "  1. nested",
+                "",
+                "  sh",
+                "    pdftotext a.pdf",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_quote_and_table() {
+        let content = "> note\n\n| a | b |\n|---|---|\n| 1 | 2 |\n";
+        assert_eq!(text(content), vec!["│ note", "", "a │ b", "1 │ 2"]);
+    }
+}
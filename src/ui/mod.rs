@@ -1,14 +1,23 @@
 //! UI rendering for the Skir TUI.
 
-mod theme;
+mod all_skills;
+mod available;
+mod detail;
+mod health;
 mod lists;
+mod markdown;
+mod preview;
+mod search;
+mod theme;
+mod update;
 
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use crate::app::{App, View};
+use crate::app::{App, Tab, View};
+use crate::plugin::LinkTarget;
 use crate::status::StatusKind;
 
 /// Main draw function.
@@ -33,7 +42,11 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
 /// Draw the title bar.
 fn draw_title(frame: &mut Frame, area: Rect) {
-    let title = Paragraph::new("skir - Plugin Manager")
+    let title = match LinkTarget::current_project().and_then(|p| p.file_name()) {
+        Some(project) => format!("skir - Plugin Manager ({})", project.to_string_lossy()),
+        None => "skir - Plugin Manager".to_string(),
+    };
+    let title = Paragraph::new(title)
         .style(Style::default().fg(theme::ACCENT).bold())
         .alignment(Alignment::Center);
     frame.render_widget(title, area);
@@ -43,8 +56,22 @@ fn draw_title(frame: &mut Frame, area: Rect) {
 fn draw_content(frame: &mut Frame, area: Rect, app: &mut App) {
     match app.view {
         View::PluginList | View::InstallInput => lists::draw_plugin_list(frame, area, app),
+        View::SkillList if app.show_skill_preview && app.tab == Tab::Skills => {
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .split(area);
+            lists::draw_skill_list(frame, chunks[0], app);
+            preview::draw_skill_preview(frame, chunks[1], app);
+        }
         View::SkillList => lists::draw_skill_list(frame, area, app),
         View::LinkTargetSelect => lists::draw_link_target_select(frame, area, app),
+        View::UpdatePreview => update::draw_update_preview(frame, area, app),
+        View::SkillDetail => detail::draw_skill_detail(frame, area, app),
+        View::Health => health::draw_health(frame, area, app),
+        View::Available | View::MarketplaceInput => available::draw_available(frame, area, app),
+        View::GlobalSearch => search::draw_global_search(frame, area, app),
+        View::AllSkills => all_skills::draw_all_skills(frame, area, app),
     }
 }
 
@@ -79,19 +106,51 @@ fn draw_help_bar(frame: &mut Frame, area: Rect, app: &App) {
 
     // Show install bar when in install input mode
     if app.view == View::InstallInput {
-        draw_install_bar(frame, area, app);
+        draw_input_bar(frame, area, "git url", &app.input);
+        return;
+    }
+    if app.view == View::MarketplaceInput {
+        draw_input_bar(frame, area, "marketplace (git url or path)", &app.input);
+        return;
+    }
+    if app.view == View::GlobalSearch {
+        draw_input_bar(frame, area, "search all skills", &app.global_query);
         return;
     }
 
     let help_text = match app.view {
-        View::PluginList => "/:search  i:install  d:delete  r:refresh  u:update  l:view  q:quit",
-        View::SkillList => "/:search  j/k:navigate  l:link  L:link all  h:back  q:quit",
-        View::LinkTargetSelect => "j/k:navigate  l/Enter:toggle  Esc/h:back  q:quit",
-        View::InstallInput => unreachable!(),
+        View::PluginList => {
+            "Tab:available  /:search  S:search all  i:install  d:delete  r:refresh  u:update  U:update all  b:rollback  H:health  A:all skills  l:view  q:quit"
+        }
+        View::SkillList if app.tab != Tab::Skills => {
+            "Tab:next tab  /:search  S:search all  j/k:navigate  l:link  L:link all  U:unlink all  h:back  q:quit"
+        }
+        View::SkillList if app.has_marks() => {
+            "Space:mark  v:range  a:all  l:link marked  L:link marked to all  U:unlink marked from all  Esc:clear marks  j/k:navigate  h:back  q:quit"
+        }
+        View::SkillList => {
+            "Tab:next tab  /:search  S:search all  j/k:navigate  Space:mark  v:range  a:all  l:link  L:link all  U:unlink all  i:info  p:preview  J/K:scroll preview  h:back  q:quit"
+        }
+        View::LinkTargetSelect => {
+            "j/k:navigate  l/Enter:toggle  u:unlink  c:copy  Esc/h:back  q:quit"
+        }
+        View::UpdatePreview => "j/k:scroll  y/Enter:accept  n/Esc:reject  q:quit",
+        View::SkillDetail => "Esc/h:back  q:quit",
+        View::Health => {
+            "j/k:navigate  f/Enter:fix broken  l:relink  d:remove  a:adopt  m:replace  A:adopt all  r:rescan  Esc/h:back  q:quit"
+        }
+        View::Available => {
+            "Tab/h:installed  /:search  S:search all  i/Enter:install  a:add marketplace  u:update  r:reload  q:quit"
+        }
+        View::AllSkills => {
+            "/:search  S:search all  j/k:navigate  l:link  u:unlink  f:filter  t:target  r:refresh  h:back  q:quit"
+        }
+        View::InstallInput | View::MarketplaceInput | View::GlobalSearch => unreachable!(),
     };
 
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(theme::TEXT_DIM))
+        .wrap(Wrap { trim: true })
         .alignment(Alignment::Center);
     frame.render_widget(help, area);
 }
@@ -99,15 +158,13 @@ fn draw_help_bar(frame: &mut Frame, area: Rect, app: &App) {
 /// Draw the search bar.
 fn draw_search_bar(frame: &mut Frame, area: Rect, app: &App) {
     let text = format!("/{}_", app.search_query);
-    let paragraph = Paragraph::new(text)
-        .style(Style::default().fg(theme::ACCENT));
+    let paragraph = Paragraph::new(text).style(Style::default().fg(theme::ACCENT));
     frame.render_widget(paragraph, area);
 }
 
-/// Draw the install input bar.
-fn draw_install_bar(frame: &mut Frame, area: Rect, app: &App) {
-    let text = format!("git url: {}_", app.input);
-    let paragraph = Paragraph::new(text)
-        .style(Style::default().fg(theme::ACCENT));
+/// Draw the input bar with a prompt.
+fn draw_input_bar(frame: &mut Frame, area: Rect, prompt: &str, input: &str) {
+    let text = format!("{}: {}_", prompt, input);
+    let paragraph = Paragraph::new(text).style(Style::default().fg(theme::ACCENT));
     frame.render_widget(paragraph, area);
 }
@@ -0,0 +1,181 @@
+//! SKILL.md preview pane rendering.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+
+use super::{markdown, theme};
+use crate::app::App;
+use crate::plugin::Skill;
+
+/// Wrap text to lines of at most `width` characters, breaking at spaces where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            // Chop words that are too long to fit on any line
+            while word.len() > width {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word.drain(..width).collect());
+            }
+            let word: String = word.into_iter().collect();
+
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Build a table of the frontmatter fields that are set.
+fn frontmatter_table(skill: &Skill, width: usize) -> Vec<Line<'static>> {
+    if let Some(error) = &skill.frontmatter_error {
+        return vec![
+            Line::styled(error.to_string(), Style::default().fg(theme::ERROR)),
+            Line::default(),
+        ];
+    }
+
+    let metadata = &skill.metadata;
+    let mut rows: Vec<(String, String)> = [
+        ("name", metadata.name.clone()),
+        ("description", metadata.description.clone()),
+        ("version", metadata.version.clone()),
+        ("license", metadata.license.clone()),
+        (
+            "allowed-tools",
+            Some(metadata.allowed_tools.join(", ")).filter(|t| !t.is_empty()),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key.to_string(), value?)))
+    .collect();
+    rows.extend(metadata.extra.iter().map(|(key, value)| {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        (key.clone(), value)
+    }));
+    if rows.is_empty() {
+        return Vec::new();
+    }
+
+    // Borders and padding take 7 columns: "│ key │ value │"
+    let key_width = rows
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let max_value_width = width.saturating_sub(key_width + 7).max(10);
+    let rows: Vec<(String, Vec<String>)> = rows
+        .into_iter()
+        .map(|(key, value)| (key, wrap(value.trim(), max_value_width)))
+        .collect();
+    let value_width = rows
+        .iter()
+        .flat_map(|(_, parts)| parts.iter().map(|p| p.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let border = |left: &str, middle: &str, right: &str| {
+        Line::styled(
+            format!(
+                "{}{}{}{}{}",
+                left,
+                "─".repeat(key_width + 2),
+                middle,
+                "─".repeat(value_width + 2),
+                right
+            ),
+            Style::default().fg(theme::BORDER),
+        )
+    };
+    let separator = |text: &'static str| Span::styled(text, Style::default().fg(theme::BORDER));
+
+    let mut lines = vec![border("┌", "┬", "┐")];
+    for (key, parts) in rows {
+        for (i, part) in parts.into_iter().enumerate() {
+            let key = if i == 0 { key.as_str() } else { "" };
+            lines.push(Line::from(vec![
+                separator("│ "),
+                Span::styled(
+                    format!("{:<key_width$}", key),
+                    Style::default().fg(theme::ACCENT),
+                ),
+                separator(" │ "),
+                Span::styled(
+                    format!("{:<value_width$}", part),
+                    Style::default().fg(theme::TEXT),
+                ),
+                separator(" │"),
+            ]));
+        }
+    }
+    lines.push(border("└", "┴", "┘"));
+    lines.push(Line::default());
+    lines
+}
+
+/// Draw the SKILL.md of the selected skill next to the skill list.
+pub fn draw_skill_preview(frame: &mut Frame, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if app.load_skill_preview().is_none() {
+        return;
+    }
+    let (Some(skill), Some(preview)) = (app.selected_skill(), app.skill_preview.as_ref()) else {
+        return;
+    };
+
+    let mut lines = frontmatter_table(skill, usize::from(inner.width));
+    lines.extend(markdown::render(&preview.content));
+
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        format!("Files ({})", preview.files.len()),
+        Style::default().fg(theme::ACCENT).bold(),
+    ));
+    if preview.files.is_empty() {
+        lines.push(Line::styled(
+            "  Only SKILL.md",
+            Style::default().fg(theme::TEXT_DIM),
+        ));
+    }
+    for file in &preview.files {
+        lines.push(Line::styled(
+            format!("  {}", file.display()),
+            Style::default().fg(theme::TEXT),
+        ));
+    }
+
+    // Keep the scroll position within the preview
+    let max_scroll = u16::try_from(lines.len().saturating_sub(1)).unwrap_or(u16::MAX);
+    let scroll = preview.scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, inner);
+
+    if let Some(preview) = &mut app.skill_preview {
+        preview.scroll = scroll;
+    }
+}
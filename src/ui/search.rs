@@ -0,0 +1,90 @@
+//! Global search rendering: ranked skills of installed plugins and marketplaces.
+
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use super::theme;
+use crate::app::App;
+use crate::plugin::SearchSource;
+
+/// Draw the results of the global search query.
+pub fn draw_global_search(frame: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(format!(
+        "Results ({} of {} indexed)  ↑/↓:select  Enter:open  Esc:back",
+        app.global_results.len(),
+        app.search_index.len()
+    ))
+    .style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    if app.global_results.is_empty() {
+        let text = if app.global_query.trim().is_empty() {
+            "Type to search skill names, descriptions and SKILL.md bodies."
+        } else {
+            "No skills match."
+        };
+        let message = Paragraph::new(text).style(Style::default().fg(theme::TEXT_DIM));
+        frame.render_widget(message, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .global_results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let doc = app.search_index.doc(result.doc);
+            let is_selected = i == app.global_selection;
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "> " } else { "  " },
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT_DIM
+                    }),
+                ),
+                Span::styled(
+                    doc.label(),
+                    Style::default().fg(if is_selected {
+                        theme::ACCENT
+                    } else {
+                        theme::TEXT
+                    }),
+                ),
+            ];
+            if let SearchSource::Available { .. } = doc.source {
+                spans.push(Span::styled(
+                    " [not installed]",
+                    Style::default().fg(theme::WARNING),
+                ));
+            }
+            if let Some(description) = doc.description.as_deref().and_then(|d| d.lines().next()) {
+                spans.push(Span::styled(
+                    format!("  {}", description),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+
+            let mut lines = vec![Line::from(spans)];
+            if let Some(snippet) = &result.snippet {
+                lines.push(Line::styled(
+                    format!("    > {}", snippet),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(app.global_selection));
+    frame.render_stateful_widget(List::new(items), chunks[1], &mut list_state);
+}
@@ -6,5 +6,6 @@ pub const TEXT: Color = Color::White;
 pub const TEXT_DIM: Color = Color::DarkGray;
 pub const ACCENT: Color = Color::Cyan;
 pub const SUCCESS: Color = Color::Green;
+pub const WARNING: Color = Color::Yellow;
 pub const ERROR: Color = Color::Red;
 pub const BORDER: Color = Color::DarkGray;
@@ -0,0 +1,105 @@
+//! Update preview rendering.
+
+use ratatui::{prelude::*, widgets::Paragraph};
+
+use super::theme;
+use crate::app::App;
+use crate::plugin::PendingUpdate;
+
+/// Shorten a commit SHA for display.
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Style a line of a unified diff.
+fn diff_line(line: &str) -> Line<'static> {
+    let color = if line.starts_with("+++") || line.starts_with("---") {
+        theme::TEXT_DIM
+    } else if line.starts_with('+') {
+        theme::SUCCESS
+    } else if line.starts_with('-') {
+        theme::ERROR
+    } else if line.starts_with("@@") {
+        theme::ACCENT
+    } else {
+        theme::TEXT
+    };
+    Line::styled(format!("    {}", line), Style::default().fg(color))
+}
+
+/// Build the lines describing a pending update.
+fn preview_lines(pending: &PendingUpdate) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        format!("Commits ({}):", pending.commits.len()),
+        Style::default().fg(theme::ACCENT).bold(),
+    )];
+    for commit in &pending.commits {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", short(&commit.sha)),
+                Style::default().fg(theme::ACCENT),
+            ),
+            Span::styled(commit.summary.clone(), Style::default().fg(theme::TEXT)),
+        ]));
+    }
+
+    lines.push(Line::default());
+    if pending.changes.is_empty() {
+        lines.push(Line::styled(
+            "No skill files changed.",
+            Style::default().fg(theme::TEXT_DIM),
+        ));
+    }
+    for change in &pending.changes {
+        let title = match &change.skill {
+            Some(skill) => format!("Skill {}:", skill),
+            None => "Other files:".to_string(),
+        };
+        lines.push(Line::styled(
+            title,
+            Style::default().fg(theme::ACCENT).bold(),
+        ));
+        for file in &change.files {
+            lines.push(Line::styled(
+                format!("  {} {}", file.status.marker(), file.path),
+                Style::default().fg(theme::TEXT),
+            ));
+            lines.extend(file.diff.lines().map(diff_line));
+        }
+        lines.push(Line::default());
+    }
+
+    lines
+}
+
+/// Draw the preview of the update currently under review.
+pub fn draw_update_preview(frame: &mut Frame, area: Rect, app: &mut App) {
+    let Some(review) = app.current_review() else {
+        return;
+    };
+
+    let header_text = format!(
+        "Update {}: {}..{} ({} more queued)",
+        review.name,
+        short(&review.pending.from),
+        short(&review.pending.to),
+        app.reviews.len() - 1,
+    );
+    let lines = preview_lines(&review.pending);
+
+    // Split area for header and preview
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(header_text).style(Style::default().fg(theme::TEXT_DIM));
+    frame.render_widget(header, chunks[0]);
+
+    // Keep the scroll position within the preview
+    let max_scroll = u16::try_from(lines.len().saturating_sub(1)).unwrap_or(u16::MAX);
+    app.preview_scroll = app.preview_scroll.min(max_scroll);
+
+    let preview = Paragraph::new(lines).scroll((app.preview_scroll, 0));
+    frame.render_widget(preview, chunks[1]);
+}